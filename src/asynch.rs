@@ -0,0 +1,289 @@
+//! Asynchronous device driver
+
+use crate::types::{BitFlagsHigh, BitFlagsLow, ConfigHigh, Register, DEVICE_BASE_ADDRESS};
+use crate::{ConversionRate, Error, SensorData, SlaveAddr};
+use core::fmt;
+use embedded_hal::i2c::{Error as _, ErrorKind};
+use embedded_hal_async::{delay::DelayNs, digital::Wait, i2c};
+use futures::stream::{self, Stream};
+
+/// Interval between data-ready polls when pacing a stream by the conversion rate.
+const POLL_INTERVAL_MS: u32 = 10;
+
+/// Error of a stream of readings paced by the DRDY pin.
+#[derive(Debug)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum StreamError<E, P> {
+    /// Error reading the sensor
+    Sensor(Error<E>),
+    /// Error waiting for the DRDY pin
+    Pin(P),
+}
+
+impl<E, P> From<Error<E>> for StreamError<E, P> {
+    fn from(error: Error<E>) -> Self {
+        StreamError::Sensor(error)
+    }
+}
+
+impl<E: fmt::Debug, P: fmt::Debug> fmt::Display for StreamError<E, P> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            StreamError::Sensor(e) => write!(f, "{}", e),
+            StreamError::Pin(e) => write!(f, "DRDY pin error: {:?}", e),
+        }
+    }
+}
+
+impl<E: fmt::Debug, P: fmt::Debug> core::error::Error for StreamError<E, P> {}
+
+/// TMP006 asynchronous device driver.
+#[derive(Debug)]
+pub struct Tmp006Async<I2C> {
+    /// The concrete I²C device implementation.
+    i2c: I2C,
+    /// The I²C device address.
+    address: u8,
+    /// Configuration register status.
+    config: ConfigHigh,
+}
+
+impl<I2C, E> Tmp006Async<I2C>
+where
+    I2C: i2c::I2c<Error = E>,
+{
+    /// Create new instance of the TMP006 device.
+    pub fn new(i2c: I2C, address: SlaveAddr) -> Self {
+        Tmp006Async {
+            i2c,
+            address: address.addr(DEVICE_BASE_ADDRESS),
+            config: ConfigHigh::default(),
+        }
+    }
+
+    /// Destroy driver instance, return I²C bus instance.
+    pub fn destroy(self) -> I2C {
+        self.i2c
+    }
+
+    /// Enable the sensor (default state).
+    ///
+    /// Note: calling this clears the data-ready bit.
+    pub async fn enable(&mut self) -> Result<(), Error<E>> {
+        let config = self.config;
+        self.write_config(config.with_high(BitFlagsHigh::MOD)).await
+    }
+
+    /// Disable the sensor (power-down).
+    ///
+    /// Note: calling this clears the data-ready bit.
+    pub async fn disable(&mut self) -> Result<(), Error<E>> {
+        let config = self.config;
+        self.write_config(config.with_low(BitFlagsHigh::MOD)).await
+    }
+
+    /// Reset the sensor (software reset).
+    ///
+    /// Note: calling this clears the data-ready bit.
+    pub async fn reset(&mut self) -> Result<(), Error<E>> {
+        let config = self.config;
+        self.write_config(config.with_high(BitFlagsHigh::SW_RESET))
+            .await
+    }
+
+    /// Enable DRDY pin.
+    ///
+    /// Note: calling this clears the data-ready bit.
+    pub async fn enable_drdy_pin(&mut self) -> Result<(), Error<E>> {
+        let config = self.config;
+        self.write_config(config.with_high(BitFlagsHigh::DRDY_EN))
+            .await
+    }
+
+    /// Disable DRDY pin.
+    ///
+    /// Note: calling this clears the data-ready bit.
+    pub async fn disable_drdy_pin(&mut self) -> Result<(), Error<E>> {
+        let config = self.config;
+        self.write_config(config.with_low(BitFlagsHigh::DRDY_EN))
+            .await
+    }
+
+    /// Set the ADC conversion rate.
+    ///
+    /// Note: calling this clears the data-ready bit.
+    pub async fn set_conversion_rate(&mut self, rate: ConversionRate) -> Result<(), Error<E>> {
+        let config = self.config;
+        self.write_config(config.with_conversion_rate(rate)).await
+    }
+
+    /// Read the data from the sensor.
+    ///
+    /// This polls the data-ready bit until a conversion is complete and then
+    /// reads the data.
+    ///
+    /// Returns `Error::Timeout` if no data is ready after twice the
+    /// conversion time, for example because the sensor is disabled.
+    pub async fn read_sensor_data<D: DelayNs>(
+        &mut self,
+        delay: &mut D,
+    ) -> Result<SensorData, Error<E>> {
+        let period_ms = self.config.conversion_rate().period_ms();
+        for _ in 0..=2 * period_ms / POLL_INTERVAL_MS {
+            match self.try_read_sensor_data().await {
+                Ok(data) => return Ok(data),
+                Err(nb::Error::Other(e)) => return Err(e),
+                Err(nb::Error::WouldBlock) => delay.delay_ms(POLL_INTERVAL_MS).await,
+            }
+        }
+        Err(Error::Timeout)
+    }
+
+    /// Read the data from the sensor if a conversion is complete.
+    async fn try_read_sensor_data(&mut self) -> nb::Result<SensorData, Error<E>> {
+        let ready = self.is_data_ready().await.map_err(nb::Error::Other)?;
+        if !ready {
+            return Err(nb::Error::WouldBlock);
+        }
+        let v = self
+            .read_register(Register::ObjectVoltage)
+            .await
+            .map_err(nb::Error::Other)?;
+        let temp = self
+            .read_register(Register::AmbientTemperature)
+            .await
+            .map_err(nb::Error::Other)?;
+        Ok(SensorData::from_registers(v, temp))
+    }
+
+    /// Reads whether there is data ready to be read.
+    pub async fn is_data_ready(&mut self) -> Result<bool, Error<E>> {
+        let config = self.read_register(Register::Config).await?;
+        Ok((config & u16::from(BitFlagsLow::DRDY)) != 0)
+    }
+
+    /// Read the manufacturer ID.
+    pub async fn read_manufacturer_id(&mut self) -> Result<u16, Error<E>> {
+        self.read_register(Register::ManufacturerId).await
+    }
+
+    /// Read the device ID.
+    pub async fn read_device_id(&mut self) -> Result<u16, Error<E>> {
+        self.read_register(Register::DeviceId).await
+    }
+
+    /// Stream of readings paced by the conversion rate.
+    ///
+    /// Each reading waits for one conversion period and then polls the
+    /// data-ready bit until the data is available.
+    /// See: [`read_sensor_data()`]. The stream never ends.
+    ///
+    /// The stream needs to be pinned before calling `next()` on it:
+    ///
+    /// ```ignore
+    /// use futures::StreamExt;
+    ///
+    /// let mut stream = core::pin::pin!(sensor.stream(delay));
+    /// while let Some(reading) = stream.next().await {
+    ///     let data = reading?;
+    ///     // ...
+    /// }
+    /// ```
+    ///
+    /// [`read_sensor_data()`]: struct.Tmp006Async.html#method.read_sensor_data
+    pub fn stream<'a, D: DelayNs + 'a>(
+        &'a mut self,
+        delay: D,
+    ) -> impl Stream<Item = Result<SensorData, Error<E>>> + 'a {
+        stream::unfold((self, delay), |(sensor, mut delay)| async move {
+            delay
+                .delay_ms(sensor.config.conversion_rate().period_ms())
+                .await;
+            let reading = sensor.read_sensor_data(&mut delay).await;
+            Some((reading, (sensor, delay)))
+        })
+    }
+
+    /// Stream of readings paced by the DRDY pin.
+    ///
+    /// Each reading waits for a falling edge on the DRDY pin, which must
+    /// have been enabled with [`enable_drdy_pin()`]. Errors waiting for the
+    /// pin are returned as `StreamError::Pin`. The stream never ends.
+    ///
+    /// [`enable_drdy_pin()`]: struct.Tmp006Async.html#method.enable_drdy_pin
+    pub fn stream_with_drdy_pin<'a, P: Wait + 'a>(
+        &'a mut self,
+        drdy: P,
+    ) -> impl Stream<Item = Result<SensorData, StreamError<E, P::Error>>> + 'a {
+        stream::unfold((self, drdy), |(sensor, mut drdy)| async move {
+            loop {
+                if let Err(e) = drdy.wait_for_falling_edge().await {
+                    return Some((Err(StreamError::Pin(e)), (sensor, drdy)));
+                }
+                match sensor.try_read_sensor_data().await {
+                    Ok(data) => return Some((Ok(data), (sensor, drdy))),
+                    Err(nb::Error::Other(e)) => return Some((Err(e.into()), (sensor, drdy))),
+                    Err(nb::Error::WouldBlock) => (),
+                }
+            }
+        })
+    }
+
+    async fn write_config(&mut self, config: ConfigHigh) -> Result<(), Error<E>> {
+        self.i2c
+            .write(self.address, &[Register::Config.address(), config.bits, 0])
+            .await
+            .map_err(|e| self.transfer_failed(e))?;
+        debug!(
+            "tmp006 0x{:02X}: wrote {:?} (0x{:02X}): 0x{:02X}00",
+            self.address,
+            Register::Config,
+            Register::Config.address(),
+            config.bits
+        );
+        // a software reset restores the default configuration
+        self.config = if config.bits & BitFlagsHigh::SW_RESET != 0 {
+            ConfigHigh::default()
+        } else {
+            config
+        };
+        debug!(
+            "tmp006 0x{:02X}: configuration: enabled: {}, conversion rate: {:?}, DRDY pin: {}",
+            self.address,
+            self.config.bits & BitFlagsHigh::MOD != 0,
+            self.config.conversion_rate(),
+            self.config.bits & BitFlagsHigh::DRDY_EN != 0
+        );
+        Ok(())
+    }
+
+    async fn read_register(&mut self, register: Register) -> Result<u16, Error<E>> {
+        let mut data = [0; 2];
+        self.i2c
+            .write_read(self.address, &[register.address()], &mut data)
+            .await
+            .map_err(|e| self.transfer_failed(e))?;
+        let value = (u16::from(data[0]) << 8) | u16::from(data[1]);
+        debug!(
+            "tmp006 0x{:02X}: read {:?} (0x{:02X}): 0x{:04X}",
+            self.address,
+            register,
+            register.address(),
+            value
+        );
+        Ok(value)
+    }
+
+    fn transfer_failed(&self, error: E) -> Error<E> {
+        warn!(
+            "tmp006 0x{:02X}: I2C transfer failed: {:?}",
+            self.address,
+            error_kind::<I2C>(&error)
+        );
+        Error::I2C(error)
+    }
+}
+
+fn error_kind<I2C: i2c::I2c>(error: &I2C::Error) -> ErrorKind {
+    error.kind()
+}
@@ -0,0 +1,732 @@
+//! Command-line tool for TMP006/B sensors on Linux.
+//!
+//! Build it with the `cli` feature:
+//! ```text
+//! cargo install tmp006 --features cli
+//! tmp006 --bus /dev/i2c-1 read --unit celsius --count 5
+//! ```
+
+use clap::{Args, Parser, Subcommand, ValueEnum};
+use embedded_hal::{delay::DelayNs, i2c::I2c};
+use linux_embedded_hal::{Delay, I2cdev};
+use serde::Serialize;
+use std::{error::Error as StdError, io::Write, time::Instant};
+use tmp006::{
+    BitFlagsHigh, Clock, ConversionRate, Register, RegisterSnapshot, SensorData, SlaveAddr, Tmp006,
+};
+
+type Result<T> = std::result::Result<T, Box<dyn StdError>>;
+
+/// Lowest device address.
+const FIRST_ADDRESS: u8 = 0x40;
+/// Highest device address.
+const LAST_ADDRESS: u8 = 0x47;
+/// Interval between data-ready polls.
+const POLL_INTERVAL_MS: u32 = 10;
+
+#[derive(Debug, Parser)]
+#[command(
+    version,
+    about = "Read and configure TMP006/B infrared temperature sensors"
+)]
+struct Cli {
+    /// I²C bus device
+    #[arg(short, long, default_value = "/dev/i2c-1")]
+    bus: String,
+    /// Device address (0x40-0x47)
+    #[arg(short, long, default_value = "0x40", value_parser = parse_address)]
+    address: u8,
+    /// Output format
+    #[arg(short, long, value_enum, default_value_t = Format::Text)]
+    format: Format,
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Debug, Subcommand)]
+enum Command {
+    /// Check that a TMP006 responds at the address
+    Probe,
+    /// Look for TMP006 devices at all possible addresses
+    Scan,
+    /// Read the temperatures
+    Read {
+        #[command(flatten)]
+        measurement: Measurement,
+        /// Number of readings
+        #[arg(short = 'n', long, default_value_t = 1)]
+        count: u32,
+    },
+    /// Continuously read the temperatures at the conversion rate
+    Monitor {
+        #[command(flatten)]
+        measurement: Measurement,
+        /// Change the conversion rate in conversions per second before
+        /// reading. The sensor keeps it afterwards
+        #[arg(short, long, value_enum)]
+        rate: Option<Rate>,
+        /// Number of readings. `0` reads until interrupted
+        #[arg(short = 'n', long, default_value_t = 0)]
+        count: u32,
+    },
+    /// Show or change the configuration
+    Config {
+        /// Enable continuous conversion
+        #[arg(long, conflicts_with = "disable")]
+        enable: bool,
+        /// Power the sensor down
+        #[arg(long)]
+        disable: bool,
+        /// Conversion rate in conversions per second
+        #[arg(short, long, value_enum)]
+        rate: Option<Rate>,
+        /// Enable or disable the DRDY pin
+        #[arg(long)]
+        drdy_pin: Option<bool>,
+    },
+    /// Print all registers
+    Dump,
+}
+
+#[derive(Debug, Args)]
+struct Measurement {
+    /// Temperature unit
+    #[arg(short, long, value_enum, default_value_t = Unit::Celsius)]
+    unit: Unit,
+    /// Calibration factor
+    #[arg(long, default_value_t = 6e-14)]
+    s0: f64,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+enum Format {
+    Text,
+    Csv,
+    Json,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+enum Unit {
+    Kelvin,
+    Celsius,
+    Fahrenheit,
+}
+
+impl Unit {
+    fn convert_kelvin(self, kelvin: f64) -> f64 {
+        match self {
+            Unit::Kelvin => kelvin,
+            Unit::Celsius => kelvin - 273.15,
+            Unit::Fahrenheit => (kelvin - 273.15) * 1.8 + 32.0,
+        }
+    }
+
+    fn symbol(self) -> &'static str {
+        match self {
+            Unit::Kelvin => "K",
+            Unit::Celsius => "°C",
+            Unit::Fahrenheit => "°F",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+enum Rate {
+    #[value(name = "4")]
+    Cps4,
+    #[value(name = "2")]
+    Cps2,
+    #[value(name = "1")]
+    Cps1,
+    #[value(name = "0.5")]
+    Cps0_5,
+    #[value(name = "0.25")]
+    Cps0_25,
+}
+
+impl From<Rate> for ConversionRate {
+    fn from(rate: Rate) -> Self {
+        match rate {
+            Rate::Cps4 => ConversionRate::Cps4,
+            Rate::Cps2 => ConversionRate::Cps2,
+            Rate::Cps1 => ConversionRate::Cps1,
+            Rate::Cps0_5 => ConversionRate::Cps0_5,
+            Rate::Cps0_25 => ConversionRate::Cps0_25,
+        }
+    }
+}
+
+fn parse_address(value: &str) -> std::result::Result<u8, String> {
+    let address = match value.strip_prefix("0x") {
+        Some(hex) => u8::from_str_radix(hex, 16),
+        None => value.parse(),
+    }
+    .map_err(|e| e.to_string())?;
+    if (FIRST_ADDRESS..=LAST_ADDRESS).contains(&address) {
+        Ok(address)
+    } else {
+        Err(format!(
+            "address must be between 0x{:02X} and 0x{:02X}",
+            FIRST_ADDRESS, LAST_ADDRESS
+        ))
+    }
+}
+
+fn slave_addr(address: u8) -> SlaveAddr {
+    let bits = address - FIRST_ADDRESS;
+    SlaveAddr::Alternative(bits & 0b100 != 0, bits & 0b010 != 0, bits & 0b001 != 0)
+}
+
+fn conversions_per_second(rate: ConversionRate) -> f64 {
+    1000.0 / f64::from(rate.period_ms())
+}
+
+/// Output record printable in all formats.
+trait Record: Serialize {
+    const CSV_HEADER: &'static str;
+    fn text(&self) -> String;
+    fn csv(&self) -> String;
+}
+
+#[derive(Debug, Serialize)]
+struct Device {
+    address: u8,
+}
+
+impl Record for Device {
+    const CSV_HEADER: &'static str = "address";
+
+    fn text(&self) -> String {
+        format!("TMP006 found at 0x{:02X}", self.address)
+    }
+
+    fn csv(&self) -> String {
+        format!("0x{:02X}", self.address)
+    }
+}
+
+#[derive(Debug, Serialize)]
+struct Reading {
+    address: u8,
+    sequence: u32,
+    object_temperature: f64,
+    ambient_temperature: f64,
+    unit: &'static str,
+    object_voltage: i16,
+}
+
+impl Record for Reading {
+    const CSV_HEADER: &'static str =
+        "address,sequence,object_temperature,ambient_temperature,unit,object_voltage";
+
+    fn text(&self) -> String {
+        format!(
+            "0x{:02X} #{}: object {:.2} {}, ambient {:.2} {}, V_OBJECT {}",
+            self.address,
+            self.sequence,
+            self.object_temperature,
+            self.unit,
+            self.ambient_temperature,
+            self.unit,
+            self.object_voltage
+        )
+    }
+
+    fn csv(&self) -> String {
+        format!(
+            "0x{:02X},{},{:.2},{:.2},{},{}",
+            self.address,
+            self.sequence,
+            self.object_temperature,
+            self.ambient_temperature,
+            self.unit,
+            self.object_voltage
+        )
+    }
+}
+
+#[derive(Debug, Serialize)]
+struct Config {
+    address: u8,
+    enabled: bool,
+    conversion_rate: f64,
+    drdy_pin: bool,
+}
+
+impl Record for Config {
+    const CSV_HEADER: &'static str = "address,enabled,conversion_rate,drdy_pin";
+
+    fn text(&self) -> String {
+        format!(
+            "0x{:02X}: {}, {} conversions/s, DRDY pin {}",
+            self.address,
+            if self.enabled { "enabled" } else { "disabled" },
+            self.conversion_rate,
+            if self.drdy_pin { "enabled" } else { "disabled" }
+        )
+    }
+
+    fn csv(&self) -> String {
+        format!(
+            "0x{:02X},{},{},{}",
+            self.address, self.enabled, self.conversion_rate, self.drdy_pin
+        )
+    }
+}
+
+impl Record for RegisterSnapshot {
+    const CSV_HEADER: &'static str =
+        "object_voltage,ambient_temperature,config,manufacturer_id,device_id";
+
+    fn text(&self) -> String {
+        self.to_string()
+    }
+
+    fn csv(&self) -> String {
+        format!(
+            "0x{:04X},0x{:04X},0x{:04X},0x{:04X},0x{:04X}",
+            self.object_voltage,
+            self.ambient_temperature,
+            self.config,
+            self.manufacturer_id,
+            self.device_id
+        )
+    }
+}
+
+struct Output<W> {
+    out: W,
+    format: Format,
+    header_written: bool,
+}
+
+impl<W: Write> Output<W> {
+    fn new(out: W, format: Format) -> Self {
+        Output {
+            out,
+            format,
+            header_written: false,
+        }
+    }
+
+    fn write<R: Record>(&mut self, record: &R) -> Result<()> {
+        match self.format {
+            Format::Text => writeln!(self.out, "{}", record.text())?,
+            Format::Csv => {
+                if !self.header_written {
+                    writeln!(self.out, "{}", R::CSV_HEADER)?;
+                    self.header_written = true;
+                }
+                writeln!(self.out, "{}", record.csv())?;
+            }
+            Format::Json => writeln!(self.out, "{}", serde_json::to_string(record)?)?,
+        }
+        self.out.flush()?;
+        Ok(())
+    }
+}
+
+/// Load the device configuration into the driver and decode it through a
+/// snapshot.
+fn read_config<I2C: I2c>(sensor: &mut Tmp006<I2C>) -> Result<RegisterSnapshot>
+where
+    I2C::Error: 'static,
+{
+    sensor.load_config()?;
+    Ok(RegisterSnapshot {
+        config: u16::from(sensor.config_register_msb()) << 8,
+        ..RegisterSnapshot::default()
+    })
+}
+
+/// Fail instead of enabling a powered-down sensor behind the user's back.
+fn ensure_enabled(snapshot: &RegisterSnapshot) -> Result<()> {
+    if snapshot.is_enabled() {
+        Ok(())
+    } else {
+        Err("the sensor is powered down, enable it with `tmp006 config --enable`".into())
+    }
+}
+
+/// Write the mode, conversion rate and DRDY pin settings in a single write
+/// if they differ from the current ones.
+fn write_config<I2C: I2c>(
+    sensor: &mut Tmp006<I2C>,
+    snapshot: &RegisterSnapshot,
+    enabled: bool,
+    rate: ConversionRate,
+    drdy_pin: bool,
+) -> Result<()>
+where
+    I2C::Error: 'static,
+{
+    let bits = |enabled, rate, drdy_pin| {
+        let mode = if enabled { BitFlagsHigh::MOD } else { 0 };
+        let drdy_en = if drdy_pin { BitFlagsHigh::DRDY_EN } else { 0 };
+        mode | (u8::from(rate) << 1) | drdy_en
+    };
+    let current = bits(
+        snapshot.is_enabled(),
+        snapshot.conversion_rate(),
+        snapshot.is_drdy_pin_enabled(),
+    );
+    let new = bits(enabled, rate, drdy_pin);
+    if new != current {
+        sensor.write_raw_register(Register::Config, u16::from(new) << 8)?;
+    }
+    Ok(())
+}
+
+fn read_data<I2C: I2c, D: DelayNs, C: Clock>(
+    sensor: &mut Tmp006<I2C>,
+    delay: &mut D,
+    clock: &mut C,
+) -> Result<SensorData>
+where
+    I2C::Error: 'static,
+{
+    let timeout_ms = 2 * ConversionRate::Cps0_25.period_ms();
+    for _ in 0..=timeout_ms / POLL_INTERVAL_MS {
+        match sensor.read_fresh_sensor_data(clock.now_ms()) {
+            Ok(data) => return Ok(data),
+            Err(nb::Error::WouldBlock) => delay.delay_ms(POLL_INTERVAL_MS),
+            Err(nb::Error::Other(e)) => return Err(e.into()),
+        }
+    }
+    Err(tmp006::Error::<I2C::Error>::Timeout.into())
+}
+
+fn read<I2C: I2c, D: DelayNs, C: Clock, W: Write>(
+    sensor: &mut Tmp006<I2C>,
+    delay: &mut D,
+    clock: &mut C,
+    output: &mut Output<W>,
+    address: u8,
+    measurement: &Measurement,
+    count: u32,
+) -> Result<()>
+where
+    I2C::Error: 'static,
+{
+    let mut read = 0;
+    while count == 0 || read < count {
+        let data = read_data(sensor, delay, clock)?;
+        let object = sensor.calculate_object_temperature(data, measurement.s0);
+        let ambient = data.ambient_temperature_celsius() + 273.15;
+        output.write(&Reading {
+            address,
+            sequence: sensor.fresh_read_count(),
+            object_temperature: measurement.unit.convert_kelvin(object),
+            ambient_temperature: measurement.unit.convert_kelvin(ambient),
+            unit: measurement.unit.symbol(),
+            object_voltage: data.object_voltage,
+        })?;
+        read += 1;
+    }
+    Ok(())
+}
+
+fn scan<I2C: I2c, W: Write>(mut i2c: I2C, output: &mut Output<W>) -> Result<()> {
+    for address in FIRST_ADDRESS..=LAST_ADDRESS {
+        let mut sensor = Tmp006::new(i2c, slave_addr(address));
+        let found = sensor.check_device_id().is_ok();
+        i2c = sensor.destroy();
+        if found {
+            output.write(&Device { address })?;
+        }
+    }
+    Ok(())
+}
+
+fn run<I2C: I2c, D: DelayNs, C: Clock, W: Write>(
+    cli: &Cli,
+    i2c: I2C,
+    delay: &mut D,
+    clock: &mut C,
+    out: W,
+) -> Result<()>
+where
+    I2C::Error: 'static,
+{
+    let mut output = Output::new(out, cli.format);
+    let address = cli.address;
+    let mut sensor = Tmp006::new(i2c, slave_addr(address));
+    match &cli.command {
+        Command::Probe => {
+            sensor.check_device_id()?;
+            output.write(&Device { address })?;
+        }
+        Command::Read { measurement, count } => {
+            ensure_enabled(&read_config(&mut sensor)?)?;
+            read(
+                &mut sensor,
+                delay,
+                clock,
+                &mut output,
+                address,
+                measurement,
+                *count,
+            )?;
+        }
+        Command::Monitor {
+            measurement,
+            rate,
+            count,
+        } => {
+            let snapshot = read_config(&mut sensor)?;
+            ensure_enabled(&snapshot)?;
+            if let Some(rate) = rate {
+                let drdy_pin = snapshot.is_drdy_pin_enabled();
+                write_config(&mut sensor, &snapshot, true, (*rate).into(), drdy_pin)?;
+            }
+            read(
+                &mut sensor,
+                delay,
+                clock,
+                &mut output,
+                address,
+                measurement,
+                *count,
+            )?;
+        }
+        Command::Config {
+            enable,
+            disable,
+            rate,
+            drdy_pin,
+        } => {
+            let snapshot = read_config(&mut sensor)?;
+            let mut config = Config {
+                address,
+                enabled: snapshot.is_enabled(),
+                conversion_rate: conversions_per_second(snapshot.conversion_rate()),
+                drdy_pin: snapshot.is_drdy_pin_enabled(),
+            };
+            if *enable || *disable || rate.is_some() || drdy_pin.is_some() {
+                let rate = rate.map_or(snapshot.conversion_rate(), ConversionRate::from);
+                config.enabled = (snapshot.is_enabled() || *enable) && !*disable;
+                config.conversion_rate = conversions_per_second(rate);
+                config.drdy_pin = drdy_pin.unwrap_or(snapshot.is_drdy_pin_enabled());
+                write_config(
+                    &mut sensor,
+                    &snapshot,
+                    config.enabled,
+                    rate,
+                    config.drdy_pin,
+                )?;
+            }
+            output.write(&config)?;
+        }
+        Command::Dump => output.write(&sensor.dump_registers()?)?,
+        Command::Scan => scan(sensor.destroy(), &mut output)?,
+    }
+    Ok(())
+}
+
+fn main() {
+    let cli = Cli::parse();
+    let start = Instant::now();
+    let mut clock = || start.elapsed().as_millis() as u64;
+    let result = I2cdev::new(&cli.bus).map_err(Into::into).and_then(|i2c| {
+        let out = std::io::stdout().lock();
+        run(&cli, i2c, &mut Delay, &mut clock, out)
+    });
+    if let Err(e) = result {
+        eprintln!("Error: {}", e);
+        std::process::exit(1);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use embedded_hal::i2c::{ErrorKind, NoAcknowledgeSource};
+    use embedded_hal_mock::eh1::{
+        delay::NoopDelay,
+        i2c::{Mock as I2cMock, Transaction as I2cTrans},
+    };
+
+    const CONFIG: u8 = 0x02;
+    const MANUFAC_ID: u8 = 0xFE;
+    const DEVICE_ID: u8 = 0xFF;
+    const CONFIG_DEFAULT: u8 = 0x74;
+    const DRDY: u8 = 0x80;
+
+    fn run_with(args: &[&str], transactions: &[I2cTrans]) -> Result<String> {
+        let cli = Cli::try_parse_from(core::iter::once("tmp006").chain(args.iter().copied()))?;
+        let mut i2c = I2cMock::new(transactions);
+        let mut out = Vec::new();
+        // advance the time by one poll interval for each poll
+        let mut now = 0;
+        let mut clock = || {
+            now += u64::from(POLL_INTERVAL_MS);
+            now
+        };
+        let result = run(&cli, i2c.clone(), &mut NoopDelay, &mut clock, &mut out);
+        i2c.done();
+        result.map(|_| String::from_utf8(out).unwrap())
+    }
+
+    fn id_reads(address: u8) -> [I2cTrans; 2] {
+        [
+            I2cTrans::write_read(address, vec![MANUFAC_ID], vec![0x54, 0x49]),
+            I2cTrans::write_read(address, vec![DEVICE_ID], vec![0x00, 0x67]),
+        ]
+    }
+
+    fn config_read(config: u8) -> I2cTrans {
+        I2cTrans::write_read(0x40, vec![CONFIG], vec![config, 0])
+    }
+
+    fn dump_reads(address: u8, config: u8) -> [I2cTrans; 5] {
+        [
+            I2cTrans::write_read(address, vec![0x00], vec![0xFF, 0x9C]),
+            I2cTrans::write_read(address, vec![0x01], vec![0x0A, 0x8C]),
+            I2cTrans::write_read(address, vec![CONFIG], vec![config, 0]),
+            I2cTrans::write_read(address, vec![MANUFAC_ID], vec![0x54, 0x49]),
+            I2cTrans::write_read(address, vec![DEVICE_ID], vec![0x00, 0x67]),
+        ]
+    }
+
+    #[test]
+    fn can_parse_addresses() {
+        assert_eq!(Ok(0x41), parse_address("0x41"));
+        assert_eq!(Ok(0x47), parse_address("71"));
+        assert!(parse_address("0x48").is_err());
+        assert_eq!(SlaveAddr::Alternative(true, false, true), slave_addr(0x45));
+    }
+
+    #[test]
+    fn can_probe() {
+        let out = run_with(&["--address", "0x42", "probe"], &id_reads(0x42)).unwrap();
+        assert_eq!("TMP006 found at 0x42\n", out);
+    }
+
+    #[test]
+    fn probe_fails_without_device() {
+        let trans = [I2cTrans::write_read(0x40, vec![MANUFAC_ID], vec![0, 0])
+            .with_error(ErrorKind::NoAcknowledge(NoAcknowledgeSource::Address))];
+        assert!(run_with(&["probe"], &trans).is_err());
+    }
+
+    #[test]
+    fn can_scan() {
+        let mut trans = Vec::new();
+        for address in FIRST_ADDRESS..=LAST_ADDRESS {
+            if address == 0x41 || address == 0x46 {
+                trans.extend(id_reads(address));
+            } else {
+                trans.push(
+                    I2cTrans::write_read(address, vec![MANUFAC_ID], vec![0, 0])
+                        .with_error(ErrorKind::NoAcknowledge(NoAcknowledgeSource::Address)),
+                );
+            }
+        }
+        let out = run_with(&["--format", "csv", "scan"], &trans).unwrap();
+        assert_eq!("address\n0x41\n0x46\n", out);
+    }
+
+    #[test]
+    fn can_read_as_json() {
+        let trans = [
+            config_read(CONFIG_DEFAULT),
+            I2cTrans::write_read(0x40, vec![CONFIG], vec![CONFIG_DEFAULT, 0]),
+            I2cTrans::write_read(0x40, vec![CONFIG], vec![CONFIG_DEFAULT, DRDY]),
+            I2cTrans::write_read(0x40, vec![0x00], vec![0xFF, 0x9C]),
+            I2cTrans::write_read(0x40, vec![0x01], vec![0x0A, 0x8C]),
+        ];
+        let out = run_with(&["--format", "json", "read", "--unit", "kelvin"], &trans).unwrap();
+        let reading: serde_json::Value = serde_json::from_str(&out).unwrap();
+        assert_eq!(64, reading["address"]);
+        assert_eq!(1, reading["sequence"]);
+        assert_eq!("K", reading["unit"]);
+        assert_eq!(-100, reading["object_voltage"]);
+        assert_eq!(21.09375 + 273.15, reading["ambient_temperature"]);
+        let object = reading["object_temperature"].as_f64().unwrap();
+        assert!((object - 296.12).abs() < 0.1);
+    }
+
+    #[test]
+    fn does_not_read_powered_down_sensor() {
+        let powered_down = CONFIG_DEFAULT & !BitFlagsHigh::MOD;
+        for command in ["read", "monitor"] {
+            let error = run_with(&[command], &[config_read(powered_down)]).unwrap_err();
+            assert!(error.to_string().contains("powered down"));
+        }
+    }
+
+    #[test]
+    fn can_monitor_at_conversion_rate() {
+        let cps4 = 0x70;
+        let mut trans = vec![
+            config_read(CONFIG_DEFAULT),
+            I2cTrans::write(0x40, vec![CONFIG, cps4, 0]),
+        ];
+        // the second conversion is identical, but read one period later
+        for _ in 0..2 {
+            trans.extend([
+                I2cTrans::write_read(0x40, vec![CONFIG], vec![cps4, DRDY]),
+                I2cTrans::write_read(0x40, vec![0x00], vec![0xFF, 0x9C]),
+                I2cTrans::write_read(0x40, vec![0x01], vec![0x0A, 0x8C]),
+            ]);
+        }
+        let args = ["--format", "csv", "monitor", "--rate", "4", "-n", "2"];
+        let out = run_with(&args, &trans).unwrap();
+        assert_eq!(
+            "address,sequence,object_temperature,ambient_temperature,unit,object_voltage\n\
+             0x40,1,22.97,21.09,°C,-100\n\
+             0x40,2,22.97,21.09,°C,-100\n",
+            out
+        );
+    }
+
+    #[test]
+    fn can_show_config() {
+        let out = run_with(&["config"], &[config_read(CONFIG_DEFAULT)]).unwrap();
+        assert_eq!("0x40: enabled, 1 conversions/s, DRDY pin disabled\n", out);
+    }
+
+    #[test]
+    fn can_set_config() {
+        let trans = [
+            config_read(CONFIG_DEFAULT),
+            I2cTrans::write(0x40, vec![CONFIG, 0x09, 0]),
+        ];
+        let args = [
+            "config",
+            "--disable",
+            "--rate",
+            "0.25",
+            "--drdy-pin",
+            "true",
+        ];
+        let out = run_with(&args, &trans).unwrap();
+        assert_eq!(
+            "0x40: disabled, 0.25 conversions/s, DRDY pin enabled\n",
+            out
+        );
+    }
+
+    #[test]
+    fn setting_rate_keeps_sensor_powered_down() {
+        let trans = [
+            config_read(0x04),
+            I2cTrans::write(0x40, vec![CONFIG, 0x00, 0]),
+        ];
+        let out = run_with(&["config", "--rate", "4"], &trans).unwrap();
+        assert_eq!("0x40: disabled, 4 conversions/s, DRDY pin disabled\n", out);
+    }
+
+    #[test]
+    fn does_not_write_unchanged_config() {
+        let out = run_with(&["config", "--rate", "1"], &[config_read(CONFIG_DEFAULT)]).unwrap();
+        assert_eq!("0x40: enabled, 1 conversions/s, DRDY pin disabled\n", out);
+    }
+
+    #[test]
+    fn can_dump_registers() {
+        let out = run_with(&["dump"], &dump_reads(0x40, CONFIG_DEFAULT)).unwrap();
+        assert!(out.starts_with("V_OBJECT  (0x00): 0xFF9C (-15.625 µV)\n"));
+        assert!(out.ends_with("DEVICE_ID (0xFF): 0x0067\n"));
+    }
+}
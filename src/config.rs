@@ -1,20 +1,35 @@
 use crate::{
-    BitFlagsHigh, ConfigHigh, ConversionRate, Error, Register, SlaveAddr, Tmp006,
-    DEVICE_BASE_ADDRESS,
+    BitFlagsHigh, ConfigHigh, ConversionRate, Error, NoDelay, Register, RetryPolicy, SlaveAddr,
+    Tmp006, DEVICE_BASE_ADDRESS,
 };
-use embedded_hal::i2c;
+use embedded_hal::{delay::DelayNs, i2c};
+
+const CONVERSION_RATE_MASK: u8 = BitFlagsHigh::CR2 | BitFlagsHigh::CR1 | BitFlagsHigh::CR0;
+/// Configuration settings: mode, conversion rate and DRDY pin.
+const SETTINGS_MASK: u8 = BitFlagsHigh::MOD | CONVERSION_RATE_MASK | BitFlagsHigh::DRDY_EN;
 
 impl ConfigHigh {
-    fn with_high(self, mask: u8) -> Self {
+    pub(crate) fn with_high(self, mask: u8) -> Self {
         ConfigHigh {
             bits: self.bits | mask,
         }
     }
-    fn with_low(self, mask: u8) -> Self {
+    pub(crate) fn with_low(self, mask: u8) -> Self {
         ConfigHigh {
             bits: self.bits & !mask,
         }
     }
+
+    pub(crate) fn conversion_rate(self) -> ConversionRate {
+        let bits = (self.bits & CONVERSION_RATE_MASK) >> 1;
+        ConversionRate::try_from(bits).unwrap_or(ConversionRate::Cps0_25)
+    }
+
+    pub(crate) fn with_conversion_rate(self, rate: ConversionRate) -> Self {
+        ConfigHigh {
+            bits: (self.bits & !CONVERSION_RATE_MASK) | (u8::from(rate) << 1),
+        }
+    }
 }
 
 impl Default for ConfigHigh {
@@ -33,11 +48,27 @@ where
     pub fn new(i2c: I2C, address: SlaveAddr) -> Self {
         Tmp006 {
             i2c,
+            delay: NoDelay,
+            retry_policy: RetryPolicy::default(),
             address: address.addr(DEVICE_BASE_ADDRESS),
             config: ConfigHigh::default(),
+            config_check_interval: 0,
+            reads_since_config_check: 0,
+            reconfiguration_count: 0,
+            verify_writes: false,
+            error_count: 0,
+            retry_count: 0,
+            next_fresh_ms: None,
+            fresh_read_count: 0,
         }
     }
+}
 
+impl<I2C, D, E> Tmp006<I2C, D>
+where
+    I2C: i2c::I2c<Error = E>,
+    D: DelayNs,
+{
     /// Destroy driver instance, return I²C bus instance.
     pub fn destroy(self) -> I2C {
         self.i2c
@@ -66,9 +97,7 @@ where
     /// Note: calling this clears the data-ready bit.
     pub fn reset(&mut self) -> Result<(), Error<E>> {
         let config = self.config;
-        self.write_config(config.with_high(BitFlagsHigh::SW_RESET))?;
-        self.config = ConfigHigh::default();
-        Ok(())
+        self.write_config(config.with_high(BitFlagsHigh::SW_RESET))
     }
 
     /// Enable DRDY pin.
@@ -91,43 +120,108 @@ where
     ///
     /// Note: calling this clears the data-ready bit.
     pub fn set_conversion_rate(&mut self, rate: ConversionRate) -> Result<(), Error<E>> {
-        use crate::BitFlagsHigh as BF;
-        use crate::ConversionRate as CR;
-        let config = match rate {
-            CR::Cps4 => self
-                .config
-                .with_low(BF::CR2)
-                .with_low(BF::CR1)
-                .with_low(BF::CR0),
-            CR::Cps2 => self
-                .config
-                .with_low(BF::CR2)
-                .with_low(BF::CR1)
-                .with_high(BF::CR0),
-            CR::Cps1 => self
-                .config
-                .with_low(BF::CR2)
-                .with_high(BF::CR1)
-                .with_low(BF::CR0),
-            CR::Cps0_5 => self
-                .config
-                .with_low(BF::CR2)
-                .with_high(BF::CR1)
-                .with_high(BF::CR0),
-            CR::Cps0_25 => self
-                .config
-                .with_high(BF::CR2)
-                .with_low(BF::CR1)
-                .with_low(BF::CR0),
+        let config = self.config;
+        self.write_config(config.with_conversion_rate(rate))
+    }
+
+    /// Ensure the device configuration matches the driver configuration.
+    ///
+    /// After a brown-out or an unexpected reset the device returns to its
+    /// default configuration. This reads the configuration register and
+    /// writes the configuration again if the mode, conversion rate or DRDY
+    /// pin settings differ.
+    ///
+    /// Returns whether the configuration was written again.
+    ///
+    /// Note: writing the configuration clears the data-ready bit.
+    pub fn ensure_configured(&mut self) -> Result<bool, Error<E>> {
+        let actual = (self.read_register(Register::Config)? >> 8) as u8;
+        if actual & SETTINGS_MASK == self.config.bits & SETTINGS_MASK {
+            return Ok(false);
+        }
+        let config = self.config;
+        self.write_config(config)?;
+        self.reconfiguration_count = self.reconfiguration_count.saturating_add(1);
+        Ok(true)
+    }
+
+    /// Read the device configuration and use it as the driver configuration.
+    ///
+    /// This is useful when the device was configured before the driver was
+    /// created, for example by a previous run of the program. Nothing is
+    /// written, so the ongoing conversion is not disturbed.
+    pub fn load_config(&mut self) -> Result<(), Error<E>> {
+        let bits = (self.read_register(Register::Config)? >> 8) as u8;
+        self.config = ConfigHigh {
+            bits: bits & !BitFlagsHigh::SW_RESET,
         };
-        self.write_config(config)
+        Ok(())
     }
 
-    fn write_config(&mut self, config: ConfigHigh) -> Result<(), Error<E>> {
-        self.i2c
-            .write(self.address, &[Register::CONFIG, config.bits, 0])
-            .map_err(Error::I2C)?;
+    /// Set the number of reads of the sensor data between automatic
+    /// configuration checks. `0` disables the automatic checks (default).
+    ///
+    /// See: [`ensure_configured()`](#method.ensure_configured)
+    pub fn set_config_check_interval(&mut self, reads: u16) {
+        self.config_check_interval = reads;
+        self.reads_since_config_check = 0;
+    }
+
+    /// Number of times the configuration was written again because the
+    /// device configuration did not match.
+    pub fn reconfiguration_count(&self) -> u32 {
+        self.reconfiguration_count
+    }
+
+    /// Enable verified writes.
+    ///
+    /// After each configuration write the configuration register is read
+    /// back and the mode, conversion rate and DRDY pin settings are compared.
+    /// On a mismatch `Error::VerificationFailed` is returned and the driver
+    /// keeps the previous configuration.
+    pub fn enable_write_verification(&mut self) {
+        self.verify_writes = true;
+    }
+
+    /// Disable verified writes (default).
+    pub fn disable_write_verification(&mut self) {
+        self.verify_writes = false;
+    }
+
+    pub(crate) fn write_config(&mut self, config: ConfigHigh) -> Result<(), Error<E>> {
+        self.transfer(|i2c, address| {
+            i2c.write(address, &[Register::Config.address(), config.bits, 0])
+        })?;
+        debug!(
+            "tmp006 0x{:02X}: wrote {:?} (0x{:02X}): 0x{:02X}00",
+            self.address,
+            Register::Config,
+            Register::Config.address(),
+            config.bits
+        );
+        // writing the configuration clears the data-ready bit
+        self.next_fresh_ms = None;
+        // a software reset restores the default configuration
+        let config = if config.bits & BitFlagsHigh::SW_RESET != 0 {
+            ConfigHigh::default()
+        } else {
+            config
+        };
+        if self.verify_writes {
+            let expected = config.bits & SETTINGS_MASK;
+            let actual = (self.read_register(Register::Config)? >> 8) as u8 & SETTINGS_MASK;
+            if actual != expected {
+                return Err(Error::VerificationFailed { expected, actual });
+            }
+        }
         self.config = config;
+        debug!(
+            "tmp006 0x{:02X}: configuration: enabled: {}, conversion rate: {:?}, DRDY pin: {}",
+            self.address,
+            self.config.bits & BitFlagsHigh::MOD != 0,
+            self.config.conversion_rate(),
+            self.config.bits & BitFlagsHigh::DRDY_EN != 0
+        );
         Ok(())
     }
 }
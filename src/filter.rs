@@ -0,0 +1,477 @@
+//! Digital filters for the sensor readings
+//!
+//! All filters have a fixed capacity and ignore non-finite values (`NaN` and
+//! infinities) so that a single bad value cannot corrupt their state. Until
+//! the first valid value is received they do not produce any output.
+//!
+//! Each filter can be configured from the conversion rate the readings are
+//! taken at.
+
+use crate::window::Window;
+use crate::{ConversionRate, SensorData};
+
+/// Digital filter.
+pub trait Filter {
+    /// Feed a new value and return the filtered value.
+    ///
+    /// Non-finite values are ignored. Returns `None` until the first valid
+    /// value has been received.
+    fn update(&mut self, value: f64) -> Option<f64>;
+
+    /// Current filtered value, if any.
+    fn value(&self) -> Option<f64>;
+
+    /// Return to the initial state.
+    fn reset(&mut self);
+}
+
+/// Moving average over the last `N` values.
+///
+/// During start-up the average is calculated over the values received so far.
+/// See [`with_duration()`](#method.with_duration) to average fewer values.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(transparent))]
+pub struct MovingAverage<const N: usize> {
+    window: Window<N>,
+}
+
+impl<const N: usize> MovingAverage<N> {
+    /// Create new moving average filter.
+    pub const fn new() -> Self {
+        MovingAverage {
+            window: Window::new(),
+        }
+    }
+
+    /// Create new moving average filter over the readings taken at the given
+    /// conversion rate during the given time in milliseconds.
+    ///
+    /// At least one and at most `N` values are averaged.
+    pub fn with_duration(rate: ConversionRate, duration_ms: u32) -> Self {
+        MovingAverage {
+            window: Window::with_capacity(window_len(rate, duration_ms)),
+        }
+    }
+
+    /// Number of values averaged once the filter has started up.
+    pub fn capacity(&self) -> usize {
+        self.window.capacity()
+    }
+}
+
+impl<const N: usize> Default for MovingAverage<N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<const N: usize> Filter for MovingAverage<N> {
+    fn update(&mut self, value: f64) -> Option<f64> {
+        if value.is_finite() {
+            self.window.push(value);
+        }
+        self.value()
+    }
+
+    fn value(&self) -> Option<f64> {
+        if self.window.len() == 0 {
+            None
+        } else {
+            Some(self.window.iter().sum::<f64>() / self.window.len() as f64)
+        }
+    }
+
+    fn reset(&mut self) {
+        self.window.clear();
+    }
+}
+
+/// Median of the last `N` values.
+///
+/// For an even number of values the mean of the two middle ones is used.
+/// See [`with_duration()`](#method.with_duration) to use fewer values.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(transparent))]
+pub struct Median<const N: usize> {
+    window: Window<N>,
+}
+
+impl<const N: usize> Median<N> {
+    /// Create new median filter.
+    pub const fn new() -> Self {
+        Median {
+            window: Window::new(),
+        }
+    }
+
+    /// Create new median filter over the readings taken at the given
+    /// conversion rate during the given time in milliseconds.
+    ///
+    /// At least one and at most `N` values are used.
+    pub fn with_duration(rate: ConversionRate, duration_ms: u32) -> Self {
+        Median {
+            window: Window::with_capacity(window_len(rate, duration_ms)),
+        }
+    }
+
+    /// Number of values the median is taken from once the filter has
+    /// started up.
+    pub fn capacity(&self) -> usize {
+        self.window.capacity()
+    }
+}
+
+impl<const N: usize> Default for Median<N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<const N: usize> Filter for Median<N> {
+    fn update(&mut self, value: f64) -> Option<f64> {
+        if value.is_finite() {
+            self.window.push(value);
+        }
+        self.value()
+    }
+
+    fn value(&self) -> Option<f64> {
+        let len = self.window.len();
+        if len == 0 {
+            return None;
+        }
+        let mut sorted = [0.0; N];
+        for (i, value) in self.window.iter().enumerate() {
+            let mut j = i;
+            while j > 0 && sorted[j - 1] > value {
+                sorted[j] = sorted[j - 1];
+                j -= 1;
+            }
+            sorted[j] = value;
+        }
+        if len % 2 == 1 {
+            Some(sorted[len / 2])
+        } else {
+            Some((sorted[len / 2 - 1] + sorted[len / 2]) / 2.0)
+        }
+    }
+
+    fn reset(&mut self) {
+        self.window.clear();
+    }
+}
+
+/// Exponential moving average.
+///
+/// The first valid value initializes the filter.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Exponential {
+    alpha: f64,
+    value: Option<f64>,
+}
+
+impl Exponential {
+    /// Create new exponential filter with the given smoothing factor.
+    ///
+    /// The smoothing factor is clamped to `[0.0..1.0]`, where `1.0` means
+    /// no filtering.
+    pub fn new(alpha: f64) -> Self {
+        Exponential {
+            alpha: if alpha.is_nan() {
+                1.0
+            } else {
+                alpha.clamp(0.0, 1.0)
+            },
+            value: None,
+        }
+    }
+
+    /// Create new exponential filter with the given time constant in
+    /// seconds for readings taken at the given conversion rate.
+    pub fn with_time_constant(rate: ConversionRate, time_constant_s: f64) -> Self {
+        let period_s = f64::from(rate.period_ms()) / 1000.0;
+        Self::new(1.0 - libm::exp(-period_s / time_constant_s))
+    }
+
+    /// Smoothing factor.
+    pub fn alpha(&self) -> f64 {
+        self.alpha
+    }
+}
+
+impl Filter for Exponential {
+    fn update(&mut self, value: f64) -> Option<f64> {
+        if value.is_finite() {
+            self.value = Some(match self.value {
+                Some(current) => current + self.alpha * (value - current),
+                None => value,
+            });
+        }
+        self.value
+    }
+
+    fn value(&self) -> Option<f64> {
+        self.value
+    }
+
+    fn reset(&mut self) {
+        self.value = None;
+    }
+}
+
+/// One-dimensional Kalman filter for a slowly changing value.
+///
+/// The first valid value initializes the estimate with the measurement noise
+/// as uncertainty.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Kalman {
+    process_variance: f64,
+    measurement_variance: f64,
+    estimate: Option<f64>,
+    error_variance: f64,
+}
+
+impl Kalman {
+    /// Create new Kalman filter given the process noise variance (expected
+    /// change between readings) and the measurement noise variance.
+    ///
+    /// The variances are clamped to positive finite values, so that the
+    /// filter gain is always defined.
+    pub fn new(process_variance: f64, measurement_variance: f64) -> Self {
+        let process_variance = clamp_variance(process_variance);
+        let measurement_variance = clamp_variance(measurement_variance);
+        Kalman {
+            process_variance,
+            measurement_variance,
+            estimate: None,
+            error_variance: measurement_variance,
+        }
+    }
+
+    /// Create new Kalman filter for readings taken at the given conversion
+    /// rate given the expected measurement noise (standard deviation) and
+    /// the expected drift of the value per second.
+    pub fn with_conversion_rate(rate: ConversionRate, noise: f64, drift_per_s: f64) -> Self {
+        let drift = drift_per_s * f64::from(rate.period_ms()) / 1000.0;
+        Self::new(drift * drift, noise * noise)
+    }
+
+    /// Current estimate variance.
+    pub fn error_variance(&self) -> f64 {
+        self.error_variance
+    }
+}
+
+impl Filter for Kalman {
+    fn update(&mut self, value: f64) -> Option<f64> {
+        if !value.is_finite() {
+            return self.estimate;
+        }
+        let estimate = match self.estimate {
+            Some(estimate) => {
+                let predicted_variance = self.error_variance + self.process_variance;
+                let gain = predicted_variance / (predicted_variance + self.measurement_variance);
+                self.error_variance = (1.0 - gain) * predicted_variance;
+                estimate + gain * (value - estimate)
+            }
+            None => {
+                self.error_variance = self.measurement_variance;
+                value
+            }
+        };
+        self.estimate = Some(estimate);
+        self.estimate
+    }
+
+    fn value(&self) -> Option<f64> {
+        self.estimate
+    }
+
+    fn reset(&mut self) {
+        self.estimate = None;
+        self.error_variance = self.measurement_variance;
+    }
+}
+
+/// Filter for the sensor data.
+///
+/// This filters the object voltage and the ambient temperature separately.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct DataFilter<F> {
+    object_voltage: F,
+    ambient_temperature: F,
+}
+
+impl<F: Filter> DataFilter<F> {
+    /// Create new sensor data filter from a filter for each value.
+    pub fn new(object_voltage: F, ambient_temperature: F) -> Self {
+        DataFilter {
+            object_voltage,
+            ambient_temperature,
+        }
+    }
+
+    /// Feed new sensor data and return the filtered data.
+    ///
+    /// The filtered values are rounded to the nearest integer.
+    pub fn update(&mut self, data: SensorData) -> Option<SensorData> {
+        let object_voltage = self.object_voltage.update(f64::from(data.object_voltage));
+        let ambient_temperature = self
+            .ambient_temperature
+            .update(f64::from(data.ambient_temperature));
+        Some(SensorData {
+            object_voltage: to_i16(object_voltage?),
+            ambient_temperature: to_i16(ambient_temperature?),
+        })
+    }
+
+    /// Return to the initial state.
+    pub fn reset(&mut self) {
+        self.object_voltage.reset();
+        self.ambient_temperature.reset();
+    }
+}
+
+/// Number of readings taken at the given conversion rate during the given
+/// time, at least one.
+fn window_len(rate: ConversionRate, duration_ms: u32) -> usize {
+    duration_ms.div_ceil(rate.period_ms()).max(1) as usize
+}
+
+/// Keep the variance in a range where the Kalman gain cannot overflow or
+/// become `0 / 0`.
+fn clamp_variance(variance: f64) -> f64 {
+    if variance.is_nan() {
+        f64::MIN_POSITIVE
+    } else {
+        variance.clamp(f64::MIN_POSITIVE, f64::MAX / 4.0)
+    }
+}
+
+fn to_i16(value: f64) -> i16 {
+    libm::round(value).clamp(f64::from(i16::MIN), f64::from(i16::MAX)) as i16
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn moving_average_starts_up_with_available_values() {
+        let mut filter = MovingAverage::<4>::new();
+        assert_eq!(None, filter.value());
+        assert_eq!(Some(2.0), filter.update(2.0));
+        assert_eq!(Some(3.0), filter.update(4.0));
+        assert_eq!(Some(3.0), filter.update(f64::NAN));
+        assert_eq!(Some(3.0), filter.update(f64::INFINITY));
+        filter.update(6.0);
+        filter.update(8.0);
+        assert_eq!(Some(7.0), filter.update(10.0));
+        filter.reset();
+        assert_eq!(None, filter.value());
+    }
+
+    #[test]
+    fn median_rejects_outliers() {
+        let mut filter = Median::<3>::new();
+        assert_eq!(Some(1.0), filter.update(1.0));
+        assert_eq!(Some(50.5), filter.update(100.0));
+        assert_eq!(Some(2.0), filter.update(2.0));
+        assert_eq!(Some(3.0), filter.update(3.0));
+        assert_eq!(Some(3.0), filter.update(f64::NAN));
+    }
+
+    #[test]
+    fn exponential_initializes_with_first_value() {
+        let mut filter = Exponential::new(0.5);
+        assert_eq!(None, filter.update(f64::NAN));
+        assert_eq!(Some(10.0), filter.update(10.0));
+        assert_eq!(Some(15.0), filter.update(20.0));
+        assert_eq!(Some(15.0), filter.update(f64::NAN));
+        assert_eq!(Some(15.0), filter.update(f64::NEG_INFINITY));
+    }
+
+    #[test]
+    fn exponential_clamps_smoothing_factor() {
+        assert_eq!(1.0, Exponential::new(2.0).alpha());
+        assert_eq!(0.0, Exponential::new(-1.0).alpha());
+        assert_eq!(1.0, Exponential::new(f64::NAN).alpha());
+    }
+
+    #[test]
+    fn exponential_time_constant_depends_on_conversion_rate() {
+        let fast = Exponential::with_time_constant(ConversionRate::Cps4, 10.0);
+        let slow = Exponential::with_time_constant(ConversionRate::Cps0_25, 10.0);
+        assert!(fast.alpha() < slow.alpha());
+        assert!((fast.alpha() - 0.0247).abs() < 1e-4);
+    }
+
+    #[test]
+    fn kalman_converges_to_constant_value() {
+        let mut filter = Kalman::with_conversion_rate(ConversionRate::Cps4, 1.0, 0.01);
+        assert_eq!(Some(30.0), filter.update(30.0));
+        let initial_variance = filter.error_variance();
+        for i in 0..100 {
+            let noise = if i % 2 == 0 { 1.0 } else { -1.0 };
+            filter.update(20.0 + noise);
+        }
+        assert!((filter.value().unwrap() - 20.0).abs() < 1.0);
+        assert!(filter.error_variance() < initial_variance);
+        let value = filter.value();
+        filter.update(f64::NAN);
+        filter.update(f64::INFINITY);
+        assert_eq!(value, filter.value());
+        filter.reset();
+        assert_eq!(None, filter.value());
+    }
+
+    #[test]
+    fn kalman_clamps_variances() {
+        for variance in [0.0, -1.0, f64::NAN, f64::INFINITY] {
+            let mut filter = Kalman::new(variance, variance);
+            filter.update(10.0);
+            let value = filter.update(20.0).unwrap();
+            assert!(value.is_finite());
+            assert!(filter.error_variance().is_finite());
+        }
+    }
+
+    #[test]
+    fn window_length_depends_on_conversion_rate() {
+        assert_eq!(8, MovingAverage::<8>::new().capacity());
+        assert_eq!(
+            4,
+            MovingAverage::<8>::with_duration(ConversionRate::Cps4, 1000).capacity()
+        );
+        assert_eq!(
+            1,
+            MovingAverage::<8>::with_duration(ConversionRate::Cps0_25, 1000).capacity()
+        );
+        assert_eq!(
+            8,
+            MovingAverage::<8>::with_duration(ConversionRate::Cps4, 60_000).capacity()
+        );
+        let mut filter = Median::<8>::with_duration(ConversionRate::Cps2, 1500);
+        assert_eq!(3, filter.capacity());
+        for value in [100.0, 1.0, 2.0, 3.0] {
+            filter.update(value);
+        }
+        assert_eq!(Some(2.0), filter.value());
+    }
+
+    #[test]
+    fn can_filter_sensor_data() {
+        let mut filter = DataFilter::new(MovingAverage::<2>::new(), MovingAverage::<2>::new());
+        let data = |object_voltage, ambient_temperature| SensorData {
+            object_voltage,
+            ambient_temperature,
+        };
+        assert_eq!(Some(data(-10, 800)), filter.update(data(-10, 800)));
+        assert_eq!(Some(data(-15, 801)), filter.update(data(-19, 801)));
+    }
+}
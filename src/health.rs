@@ -0,0 +1,331 @@
+//! Sensor health monitoring
+
+use crate::{Error, ErrorKind, SensorData, Tmp006};
+use embedded_hal::{delay::DelayNs, i2c};
+
+/// Lowest specified ambient temperature in °C.
+const AMBIENT_MIN_CELSIUS: f64 = -40.0;
+/// Highest specified ambient temperature in °C.
+const AMBIENT_MAX_CELSIUS: f64 = 125.0;
+
+/// Sensor fault.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum Fault {
+    /// I²C bus error
+    I2C,
+    /// Timed out waiting for the device
+    Timeout,
+    /// Configuration read back does not match
+    VerificationFailed,
+    /// The device does not identify itself as a TMP006
+    InvalidDeviceId,
+    /// Identical data for the configured number of consecutive samples
+    Stuck,
+    /// Object voltage at the limit of its range
+    Saturated,
+    /// Ambient temperature outside the specified -40°C to +125°C range
+    AmbientOutOfRange,
+    /// Data not ready for the configured number of consecutive polls
+    DataNotReady,
+}
+
+/// Sensor health.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum Health {
+    /// The last reading was plausible (default)
+    #[default]
+    Ok,
+    /// The last reading was faulty
+    Faulty(Fault),
+}
+
+/// Health monitoring counters.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct HealthCounters {
+    /// Number of plausible readings.
+    pub ok: u32,
+    /// Number of I²C bus errors.
+    pub i2c: u32,
+    /// Number of timeouts.
+    pub timeout: u32,
+    /// Number of failed configuration verifications.
+    pub verification_failed: u32,
+    /// Number of invalid device IDs.
+    pub invalid_device_id: u32,
+    /// Number of stuck readings.
+    pub stuck: u32,
+    /// Number of saturated readings.
+    pub saturated: u32,
+    /// Number of readings with the ambient temperature out of range.
+    pub ambient_out_of_range: u32,
+    /// Number of times the data was not ready for too long.
+    pub data_not_ready: u32,
+}
+
+/// Sensor health monitoring.
+///
+/// This classifies each reading as plausible or faulty and keeps counters
+/// as well as the last fault.
+///
+/// The data is compared in its raw form to detect stuck data, so that no
+/// change is lost to the decoding.
+///
+/// Reading the data does not clear the data-ready bit, so the data should
+/// be read at most once per conversion to avoid reporting it as stuck.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct HealthMonitor {
+    stuck_limit: u16,
+    not_ready_limit: u16,
+    last_data: Option<(u16, u16)>,
+    identical: u16,
+    not_ready: u16,
+    health: Health,
+    last_fault: Option<Fault>,
+    counters: HealthCounters,
+}
+
+impl HealthMonitor {
+    /// Create new health monitor.
+    ///
+    /// The data is considered stuck after `stuck_limit` consecutive
+    /// identical readings and not ready after `not_ready_limit` consecutive
+    /// polls without data. A limit of `0` disables the check.
+    pub fn new(stuck_limit: u16, not_ready_limit: u16) -> Self {
+        HealthMonitor {
+            stuck_limit,
+            not_ready_limit,
+            last_data: None,
+            identical: 0,
+            not_ready: 0,
+            health: Health::Ok,
+            last_fault: None,
+            counters: HealthCounters::default(),
+        }
+    }
+
+    /// Health as of the last classified reading.
+    pub fn health(&self) -> Health {
+        self.health
+    }
+
+    /// Last fault, if any.
+    pub fn last_fault(&self) -> Option<Fault> {
+        self.last_fault
+    }
+
+    /// Health monitoring counters.
+    pub fn counters(&self) -> HealthCounters {
+        self.counters
+    }
+
+    /// Classify the result of reading the raw `V_OBJECT` and `T_AMBIENT`
+    /// register contents.
+    ///
+    /// Returns `None` if the data was not ready and the not-ready limit has
+    /// not been reached.
+    pub fn check<E>(&mut self, result: &nb::Result<(u16, u16), Error<E>>) -> Option<Health> {
+        let health = match result {
+            Ok(raw) => {
+                self.not_ready = 0;
+                self.classify(*raw)
+            }
+            Err(nb::Error::WouldBlock) => {
+                self.not_ready = self.not_ready.saturating_add(1);
+                if self.not_ready_limit == 0 || self.not_ready < self.not_ready_limit {
+                    return None;
+                }
+                self.not_ready = 0;
+                Health::Faulty(Fault::DataNotReady)
+            }
+            Err(nb::Error::Other(e)) => {
+                self.not_ready = 0;
+                Health::Faulty(match e.kind() {
+                    ErrorKind::I2C => Fault::I2C,
+                    ErrorKind::Timeout => Fault::Timeout,
+                    ErrorKind::VerificationFailed => Fault::VerificationFailed,
+                    ErrorKind::InvalidDeviceId => Fault::InvalidDeviceId,
+                })
+            }
+        };
+        self.record(health);
+        Some(health)
+    }
+
+    /// Read the sensor data and classify the result.
+    ///
+    /// See: [`check()`].
+    ///
+    /// [`check()`]: struct.HealthMonitor.html#method.check
+    pub fn read<I2C, D, E>(
+        &mut self,
+        sensor: &mut Tmp006<I2C, D>,
+    ) -> nb::Result<SensorData, Error<E>>
+    where
+        I2C: i2c::I2c<Error = E>,
+        D: DelayNs,
+    {
+        let result = sensor.read_raw_sensor_data();
+        self.check(&result);
+        result.map(|(v, temp)| SensorData::from_registers(v, temp))
+    }
+
+    fn classify(&mut self, raw: (u16, u16)) -> Health {
+        if self.last_data == Some(raw) {
+            self.identical = self.identical.saturating_add(1);
+        } else {
+            self.identical = 1;
+        }
+        self.last_data = Some(raw);
+        let data = SensorData::from_registers(raw.0, raw.1);
+
+        let ambient = data.ambient_temperature_celsius();
+        if !(AMBIENT_MIN_CELSIUS..=AMBIENT_MAX_CELSIUS).contains(&ambient) {
+            Health::Faulty(Fault::AmbientOutOfRange)
+        } else if data.object_voltage == i16::MIN || data.object_voltage == i16::MAX {
+            Health::Faulty(Fault::Saturated)
+        } else if self.stuck_limit != 0 && self.identical >= self.stuck_limit {
+            Health::Faulty(Fault::Stuck)
+        } else {
+            Health::Ok
+        }
+    }
+
+    fn record(&mut self, health: Health) {
+        let counter = match health {
+            Health::Ok => &mut self.counters.ok,
+            Health::Faulty(fault) => {
+                self.last_fault = Some(fault);
+                match fault {
+                    Fault::I2C => &mut self.counters.i2c,
+                    Fault::Timeout => &mut self.counters.timeout,
+                    Fault::VerificationFailed => &mut self.counters.verification_failed,
+                    Fault::InvalidDeviceId => &mut self.counters.invalid_device_id,
+                    Fault::Stuck => &mut self.counters.stuck,
+                    Fault::Saturated => &mut self.counters.saturated,
+                    Fault::AmbientOutOfRange => &mut self.counters.ambient_out_of_range,
+                    Fault::DataNotReady => &mut self.counters.data_not_ready,
+                }
+            }
+        };
+        *counter = counter.saturating_add(1);
+        self.health = health;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    type Result = nb::Result<(u16, u16), Error<()>>;
+
+    fn data(object_voltage: i16, ambient_temperature: i16) -> Result {
+        Ok(SensorData {
+            object_voltage,
+            ambient_temperature,
+        }
+        .to_registers())
+    }
+
+    #[test]
+    fn plausible_data_is_ok() {
+        let mut monitor = HealthMonitor::new(3, 3);
+        assert_eq!(Some(Health::Ok), monitor.check(&data(-100, 800)));
+        assert_eq!(Some(Health::Ok), monitor.check(&data(-101, 800)));
+        assert_eq!(2, monitor.counters().ok);
+        assert_eq!(None, monitor.last_fault());
+    }
+
+    #[test]
+    fn detects_stuck_data() {
+        let mut monitor = HealthMonitor::new(3, 0);
+        assert_eq!(Some(Health::Ok), monitor.check(&data(5, 800)));
+        assert_eq!(Some(Health::Ok), monitor.check(&data(5, 800)));
+        let stuck = Some(Health::Faulty(Fault::Stuck));
+        assert_eq!(stuck, monitor.check(&data(5, 800)));
+        assert_eq!(stuck, monitor.check(&data(5, 800)));
+        assert_eq!(Some(Health::Ok), monitor.check(&data(6, 800)));
+        for raw in [800 << 2 | 1, 800 << 2 | 2] {
+            let result: Result = Ok((6, raw));
+            assert_eq!(Some(Health::Ok), monitor.check(&result));
+        }
+        assert_eq!(2, monitor.counters().stuck);
+        assert_eq!(Some(Fault::Stuck), monitor.last_fault());
+        assert_eq!(Health::Ok, monitor.health());
+    }
+
+    #[test]
+    fn detects_implausible_values() {
+        let mut monitor = HealthMonitor::new(0, 0);
+        let saturated = Some(Health::Faulty(Fault::Saturated));
+        assert_eq!(saturated, monitor.check(&data(i16::MAX, 800)));
+        assert_eq!(saturated, monitor.check(&data(i16::MIN, 800)));
+        let out_of_range = Some(Health::Faulty(Fault::AmbientOutOfRange));
+        assert_eq!(out_of_range, monitor.check(&data(0, -1281)));
+        assert_eq!(out_of_range, monitor.check(&data(0, 4001)));
+        assert_eq!(Some(Health::Ok), monitor.check(&data(0, -1280)));
+        assert_eq!(Some(Health::Ok), monitor.check(&data(0, 4000)));
+        assert_eq!(2, monitor.counters().saturated);
+        assert_eq!(2, monitor.counters().ambient_out_of_range);
+    }
+
+    #[test]
+    fn detects_bus_errors_and_missing_data() {
+        let mut monitor = HealthMonitor::new(0, 2);
+        let not_ready: Result = Err(nb::Error::WouldBlock);
+        assert_eq!(None, monitor.check(&not_ready));
+        assert_eq!(
+            Some(Health::Faulty(Fault::DataNotReady)),
+            monitor.check(&not_ready)
+        );
+        assert_eq!(None, monitor.check(&not_ready));
+        let bus_error: Result = Err(nb::Error::Other(Error::I2C(())));
+        assert_eq!(Some(Health::Faulty(Fault::I2C)), monitor.check(&bus_error));
+        assert_eq!(None, monitor.check(&not_ready));
+        assert_eq!(1, monitor.counters().i2c);
+        assert_eq!(1, monitor.counters().data_not_ready);
+        assert_eq!(Some(Fault::I2C), monitor.last_fault());
+    }
+
+    #[test]
+    fn classifies_other_errors() {
+        let mut monitor = HealthMonitor::new(0, 0);
+        let errors = [
+            (Error::Timeout, Fault::Timeout),
+            (
+                Error::VerificationFailed {
+                    expected: 0x74,
+                    actual: 0,
+                },
+                Fault::VerificationFailed,
+            ),
+            (
+                Error::InvalidDeviceId {
+                    manufacturer_id: 0,
+                    device_id: 0,
+                },
+                Fault::InvalidDeviceId,
+            ),
+        ];
+        for (error, fault) in errors {
+            let result: Result = Err(nb::Error::Other(error));
+            assert_eq!(Some(Health::Faulty(fault)), monitor.check(&result));
+        }
+        let counters = monitor.counters();
+        assert_eq!(
+            (0, 1, 1, 1),
+            (
+                counters.i2c,
+                counters.timeout,
+                counters.verification_failed,
+                counters.invalid_device_id
+            )
+        );
+    }
+}
@@ -8,24 +8,66 @@
 //! - Enable/disable the device. See: [`enable()`].
 //! - Read the object temperature. See: [`read_object_temperature()`].
 //! - Read the object voltage and ambient temperature raw data. See: [`read_sensor_data()`].
+//! - Read the raw data from a single conversion. See: [`read_sensor_data_coherent()`].
+//! - Read the ambient temperature or object voltage alone. See: [`read_ambient_temperature()`].
+//! - Read each conversion only once. See: [`read_fresh_sensor_data()`].
+//! - Perform a single measurement and power down afterwards. See: [`measure_once()`].
+//! - Plan and run duty-cycled measurements within a power budget. See: [`Scheduler`].
+//! - Read timestamped samples and detect missed conversions. See: [`Sampler`].
+//! - Stream readings asynchronously (`async` feature). See: [`Tmp006Async`].
+//! - Filter the readings. See: [`filter`].
+//! - Calculate rolling statistics over the readings. See: [`Statistics`].
+//! - Monitor temperature thresholds with hysteresis. See: [`ThresholdMonitor`].
+//! - Detect thermal settling after enabling the sensor. See: [`SettlingDetector`].
+//! - Detect objects appearing and leaving. See: [`PresenceDetector`].
+//! - Monitor the sensor health. See: [`HealthMonitor`].
 //! - Calculate the object temperature from the sensor raw data. See: [`calculate_object_temperature()`].
 //! - Set the ADC conversion rate. See: [`set_conversion_rate()`].
 //! - Enable/disable the DRDY pin. See: [`enable_drdy_pin()`].
 //! - Read whether data is ready to be read. See: [`is_data_ready()`].
 //! - Perform a software reset. See: [`reset()`].
+//! - Restore the configuration after an unexpected reset. See: [`ensure_configured()`].
+//! - Retry transfers after transient I²C bus errors. See: [`with_retry_policy()`].
+//! - Read back the configuration after writing it. See: [`enable_write_verification()`].
 //! - Read the manufacturer ID. See: [`read_manufacturer_id()`].
 //! - Read the device ID. See: [`read_device_id()`].
+//! - Check that the device is a TMP006. See: [`check_device_id()`].
+//! - Dump all registers for debugging. See: [`dump_registers()`].
+//! - Serialize and deserialize the data and configuration types (`serde` feature).
+//! - Log the error, data and configuration types with `defmt` (`defmt` feature).
+//! - Log register accesses and I²C errors (`log` and `tracing` features).
+//! - Read and write raw registers (`unstable-registers` feature).
+//!   See: [`read_raw_register()`].
 //!
 //! [`enable()`]: struct.Tmp006.html#method.enable
 //! [`read_object_temperature()`]: struct.Tmp006.html#method.read_object_temperature
 //! [`read_sensor_data()`]: struct.Tmp006.html#method.read_sensor_data
+//! [`read_sensor_data_coherent()`]: struct.Tmp006.html#method.read_sensor_data_coherent
+//! [`read_ambient_temperature()`]: struct.Tmp006.html#method.read_ambient_temperature
+//! [`read_fresh_sensor_data()`]: struct.Tmp006.html#method.read_fresh_sensor_data
+//! [`measure_once()`]: struct.Tmp006.html#method.measure_once
+//! [`Scheduler`]: struct.Scheduler.html
+//! [`Sampler`]: struct.Sampler.html
+//! [`Tmp006Async`]: struct.Tmp006Async.html
+//! [`filter`]: filter/index.html
+//! [`Statistics`]: struct.Statistics.html
+//! [`ThresholdMonitor`]: struct.ThresholdMonitor.html
+//! [`SettlingDetector`]: struct.SettlingDetector.html
+//! [`PresenceDetector`]: struct.PresenceDetector.html
+//! [`HealthMonitor`]: struct.HealthMonitor.html
 //! [`calculate_object_temperature()`]: struct.Tmp006.html#method.calculate_object_temperature
 //! [`set_conversion_rate()`]: struct.Tmp006.html#method.set_conversion_rate
 //! [`enable_drdy_pin()`]: struct.Tmp006.html#method.enable_drdy_pin
 //! [`is_data_ready()`]: struct.Tmp006.html#method.is_data_ready
 //! [`reset()`]: struct.Tmp006.html#method.reset
+//! [`ensure_configured()`]: struct.Tmp006.html#method.ensure_configured
+//! [`with_retry_policy()`]: struct.Tmp006.html#method.with_retry_policy
+//! [`enable_write_verification()`]: struct.Tmp006.html#method.enable_write_verification
 //! [`read_manufacturer_id()`]: struct.Tmp006.html#method.read_manufacturer_id
 //! [`read_device_id()`]: struct.Tmp006.html#method.read_device_id
+//! [`check_device_id()`]: struct.Tmp006.html#method.check_device_id
+//! [`dump_registers()`]: struct.Tmp006.html#method.dump_registers
+//! [`read_raw_register()`]: struct.Tmp006.html#method.read_raw_register
 //!
 //! [Introductory blog post](https://blog.eldruin.com/tmp006-contact-less-infrared-ir-thermopile-driver-in-rust/)
 //!
@@ -46,9 +88,35 @@
 //!
 //! Datasheet:
 //! - [TMP006/B](https://media.digikey.com/pdf/Data%20Sheets/Texas%20Instruments%20PDFs/TMP006(B).pdf)
+//!
 //! User guide:
 //! - [TMP006 user guide](https://cdn-shop.adafruit.com/datasheets/tmp006ug.pdf)
 //!
+//! ## Features
+//!
+//! - `async`: Asynchronous driver based on the [`embedded-hal-async`] traits
+//!   providing a [`futures`] `Stream` of readings.
+//! - `unstable-registers`: Raw register access. The register map and these
+//!   methods are not covered by the semantic versioning guarantees.
+//! - `serde`: [`serde`] `Serialize` and `Deserialize` implementations for the
+//!   data, configuration and event types as well as the state of the filters,
+//!   monitors and detectors.
+//! - `defmt`: [`defmt`] `Format` implementations for the error, data,
+//!   configuration and event types.
+//! - `log`: Debug messages for every register access and configuration
+//!   change as well as warnings for I²C errors through [`log`].
+//! - `tracing`: The same messages as events through [`tracing`].
+//! - `cli`: `tmp006` command-line tool for Linux to probe, read, monitor,
+//!   configure and dump sensors with text, CSV or JSON output. This enables
+//!   `unstable-registers`.
+//!
+//! [`embedded-hal-async`]: https://docs.rs/embedded-hal-async
+//! [`futures`]: https://docs.rs/futures
+//! [`serde`]: https://docs.rs/serde
+//! [`defmt`]: https://docs.rs/defmt
+//! [`log`]: https://docs.rs/log
+//! [`tracing`]: https://docs.rs/tracing
+//!
 //! ## Usage examples (see also examples folder)
 //!
 //! To use this driver, import this crate and an `embedded_hal` implementation,
@@ -105,6 +173,118 @@
 //! println!("Temperature: {}K", temp);
 //! ```
 //!
+//! ### Perform a single measurement and power down afterwards
+//!
+//! ```no_run
+//! use linux_embedded_hal::{Delay, I2cdev};
+//! use tmp006::{Tmp006, SlaveAddr};
+//!
+//! let dev = I2cdev::new("/dev/i2c-1").unwrap();
+//! let mut sensor = Tmp006::new(dev, SlaveAddr::default());
+//! let data = sensor.measure_once(&mut Delay).unwrap();
+//! let calibration_factor = 6e-14;
+//! let temp = sensor.calculate_object_temperature(data, calibration_factor);
+//! println!("Temperature: {}K", temp);
+//! ```
+//!
+//! ### Sample once per minute with an average current below 5 µA
+//!
+//! ```no_run
+//! use linux_embedded_hal::{Delay, I2cdev};
+//! use tmp006::{Scheduler, Tmp006, SlaveAddr};
+//!
+//! let dev = I2cdev::new("/dev/i2c-1").unwrap();
+//! let mut sensor = Tmp006::new(dev, SlaveAddr::default());
+//! let scheduler = Scheduler::new(60_000, 5.0).unwrap();
+//! println!(
+//!     "Conversion rate: {:?}, average current: {}µA, samples per hour: {}",
+//!     scheduler.conversion_rate(),
+//!     scheduler.average_current_ua(),
+//!     scheduler.samples_per_hour());
+//! loop {
+//!     let data = scheduler.sample(&mut sensor, &mut Delay).unwrap();
+//!     println!("{:?}", data);
+//! }
+//! ```
+//!
+//! ### Read timestamped samples and detect missed conversions
+//!
+//! ```no_run
+//! use linux_embedded_hal::I2cdev;
+//! use nb::block;
+//! use std::time::Instant;
+//! use tmp006::{Sampler, Tmp006, SlaveAddr};
+//!
+//! let dev = I2cdev::new("/dev/i2c-1").unwrap();
+//! let sensor = Tmp006::new(dev, SlaveAddr::default());
+//! let start = Instant::now();
+//! let mut sampler = Sampler::new(sensor, || start.elapsed().as_millis() as u64);
+//! loop {
+//!     let sample = block!(sampler.read()).unwrap();
+//!     if sample.missed > 0 {
+//!         println!("Missed {} conversions", sample.missed);
+//!     }
+//!     println!("{}ms #{}: {:?}", sample.timestamp, sample.sequence, sample.data);
+//!     // wait until the next conversion is due...
+//! }
+//! ```
+//!
+//! ### Filter the object temperature
+//!
+//! ```no_run
+//! use linux_embedded_hal::I2cdev;
+//! use nb::block;
+//! use tmp006::{filter::{Filter, Median}, ConversionRate, Tmp006, SlaveAddr};
+//!
+//! let dev = I2cdev::new("/dev/i2c-1").unwrap();
+//! let mut sensor = Tmp006::new(dev, SlaveAddr::default());
+//! sensor.set_conversion_rate(ConversionRate::Cps4).unwrap();
+//! let mut filter = Median::<5>::new();
+//! loop {
+//!     let temperature = block!(sensor.read_object_temperature(6e-14)).unwrap();
+//!     if let Some(filtered) = filter.update(temperature) {
+//!         println!("Temperature: {}K", filtered);
+//!     }
+//!     // wait until the next conversion is due...
+//! }
+//! ```
+//!
+//! ### Monitor the object temperature
+//!
+//! ```no_run
+//! use linux_embedded_hal::I2cdev;
+//! use nb::block;
+//! use tmp006::{Limits, ThresholdEvent, ThresholdMonitor, Tmp006, SlaveAddr};
+//!
+//! let dev = I2cdev::new("/dev/i2c-1").unwrap();
+//! let mut sensor = Tmp006::new(dev, SlaveAddr::default());
+//! let limits = Limits { low: 0.0, high: 40.0, hysteresis: 1.0, debounce: 3 };
+//! let mut monitor = ThresholdMonitor::new(Some(limits), None);
+//! loop {
+//!     let events = block!(monitor.poll(&mut sensor, 6e-14)).unwrap();
+//!     if events.object == Some(ThresholdEvent::EnteredHigh) {
+//!         println!("Object too hot!");
+//!     }
+//!     // wait until the next conversion is due...
+//! }
+//! ```
+//!
+//! ### Retry transfers after transient I²C bus errors
+//!
+//! ```no_run
+//! use linux_embedded_hal::{Delay, I2cdev};
+//! use nb::block;
+//! use tmp006::{RetryPolicy, Tmp006, SlaveAddr};
+//!
+//! let dev = I2cdev::new("/dev/i2c-1").unwrap();
+//! // retry up to 3 times after 100µs, 200µs and 400µs
+//! let policy = RetryPolicy::new(3, 100);
+//! let mut sensor = Tmp006::new(dev, SlaveAddr::default()).with_retry_policy(policy, Delay);
+//! let temperature = block!(sensor.read_object_temperature(6e-14)).unwrap();
+//! println!("Temperature: {}K", temperature);
+//! println!("Errors: {}, retries: {}", sensor.error_count(), sensor.retry_count());
+//! ```
+//!
 //! ### Set the conversion rate to 2 per second
 //!
 //! ```no_run
@@ -145,9 +325,46 @@
 #![deny(missing_docs, unsafe_code)]
 #![no_std]
 
+#[macro_use]
+mod macros;
+
 mod types;
-use crate::types::{BitFlagsHigh, BitFlagsLow, ConfigHigh, Register, DEVICE_BASE_ADDRESS};
-pub use crate::types::{ConversionRate, Error, SensorData, SlaveAddr, Tmp006};
+#[cfg(not(feature = "unstable-registers"))]
+use crate::types::{BitFlagsHigh, BitFlagsLow, Register};
+use crate::types::{ConfigHigh, DEVICE_BASE_ADDRESS, DEVICE_ID, MANUFACTURER_ID};
+pub use crate::types::{
+    ConversionRate, Error, ErrorKind, InvalidConversionRate, SensorData, SlaveAddr, Tmp006,
+};
 
+#[cfg(feature = "async")]
+mod asynch;
 mod config;
+pub mod filter;
+mod health;
+pub use crate::health::{Fault, Health, HealthCounters, HealthMonitor};
+mod presence;
+pub use crate::presence::{PresenceDetector, PresenceEvent};
 mod reading;
+#[cfg(feature = "async")]
+pub use crate::asynch::{StreamError, Tmp006Async};
+#[cfg(feature = "unstable-registers")]
+pub use crate::types::{BitFlagsHigh, BitFlagsLow, Register};
+#[cfg(feature = "unstable-registers")]
+mod registers;
+mod retry;
+pub use crate::retry::{NoDelay, RetryPolicy};
+mod sampler;
+pub use crate::sampler::{Clock, Sample, Sampler};
+mod scheduler;
+pub use crate::scheduler::Scheduler;
+mod settling;
+pub use crate::settling::{SettlingDetector, SettlingState};
+mod snapshot;
+pub use crate::snapshot::RegisterSnapshot;
+mod statistics;
+pub use crate::statistics::{DataStatistics, Statistics};
+mod threshold;
+pub use crate::threshold::{
+    Limits, MonitorEvents, Threshold, ThresholdEvent, ThresholdMonitor, ThresholdState,
+};
+mod window;
@@ -0,0 +1,211 @@
+//! Presence detection
+
+use crate::filter::{Exponential, Filter};
+use crate::{Error, Tmp006};
+use embedded_hal::{delay::DelayNs, i2c};
+
+/// Presence event.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum PresenceEvent {
+    /// The object temperature departed from the baseline.
+    ObjectAppeared,
+    /// The object temperature returned to the baseline.
+    ObjectLeft,
+}
+
+/// Presence detection based on step changes of the object temperature.
+///
+/// The baseline temperature of the scene is tracked with an exponential
+/// filter while no object is present and frozen otherwise. An object appears
+/// when the temperature differs from the baseline by more than `delta` and
+/// leaves when the difference falls below `delta - hysteresis`.
+///
+/// The first reading initializes the baseline, so it should be taken
+/// without any object present.
+///
+/// Since the baseline is frozen while an object is present, a slow drift of
+/// the scene temperature can keep the detector in the present state. Set a
+/// timeout to take the temperature as the new baseline after some time.
+/// See: [`with_timeout()`](#method.with_timeout).
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PresenceDetector {
+    baseline: Exponential,
+    delta: f64,
+    hysteresis: f64,
+    timeout: Option<u32>,
+    present: bool,
+    present_count: u32,
+}
+
+impl PresenceDetector {
+    /// Create new presence detector.
+    ///
+    /// The baseline filter determines how fast the baseline adapts.
+    /// See: [`Exponential::with_time_constant()`].
+    ///
+    /// The hysteresis is clamped to `[0.0..delta / 2]`, so that an object can
+    /// always leave.
+    ///
+    /// [`Exponential::with_time_constant()`]: filter/struct.Exponential.html#method.with_time_constant
+    pub fn new(delta: f64, hysteresis: f64, baseline: Exponential) -> Self {
+        let hysteresis = if hysteresis.is_nan() {
+            0.0
+        } else {
+            hysteresis.min(delta / 2.0).max(0.0)
+        };
+        PresenceDetector {
+            baseline,
+            delta,
+            hysteresis,
+            timeout: None,
+            present: false,
+            present_count: 0,
+        }
+    }
+
+    /// Take the temperature as the new baseline once an object has been
+    /// present for the given number of readings.
+    ///
+    /// `ObjectLeft` is then reported, as the object is considered part of
+    /// the scene from then on.
+    pub fn with_timeout(mut self, readings: u32) -> Self {
+        self.timeout = Some(readings);
+        self
+    }
+
+    /// Hysteresis after clamping.
+    pub fn hysteresis(&self) -> f64 {
+        self.hysteresis
+    }
+
+    /// Current baseline temperature, if any.
+    pub fn baseline(&self) -> Option<f64> {
+        self.baseline.value()
+    }
+
+    /// Whether an object is present.
+    pub fn is_present(&self) -> bool {
+        self.present
+    }
+
+    /// Evaluate a new object temperature.
+    ///
+    /// Non-finite values are ignored.
+    pub fn update(&mut self, temperature: f64) -> Option<PresenceEvent> {
+        if !temperature.is_finite() {
+            return None;
+        }
+        let baseline = match self.baseline.value() {
+            Some(baseline) => baseline,
+            None => {
+                self.baseline.update(temperature);
+                return None;
+            }
+        };
+        let difference = libm::fabs(temperature - baseline);
+        if self.present {
+            self.present_count = self.present_count.saturating_add(1);
+            let timed_out = self.timeout.is_some_and(|t| self.present_count >= t);
+            if timed_out {
+                self.baseline.reset();
+                self.baseline.update(temperature);
+            }
+            if timed_out || difference < self.delta - self.hysteresis {
+                self.present = false;
+                return Some(PresenceEvent::ObjectLeft);
+            }
+        } else if difference > self.delta {
+            self.present = true;
+            self.present_count = 0;
+            return Some(PresenceEvent::ObjectAppeared);
+        } else {
+            self.baseline.update(temperature);
+        }
+        None
+    }
+
+    /// Read the object temperature and evaluate it.
+    ///
+    /// See: [`read_object_temperature()`].
+    ///
+    /// [`read_object_temperature()`]: struct.Tmp006.html#method.read_object_temperature
+    pub fn poll<I2C, D, E>(
+        &mut self,
+        sensor: &mut Tmp006<I2C, D>,
+        calibration_factor: f64,
+    ) -> nb::Result<Option<PresenceEvent>, Error<E>>
+    where
+        I2C: i2c::I2c<Error = E>,
+        D: DelayNs,
+    {
+        let temperature = sensor.read_object_temperature(calibration_factor)?;
+        Ok(self.update(temperature))
+    }
+
+    /// Forget the baseline and the presence state.
+    pub fn reset(&mut self) {
+        self.baseline.reset();
+        self.present = false;
+        self.present_count = 0;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reports_object_appearing_and_leaving() {
+        let mut detector = PresenceDetector::new(2.0, 0.5, Exponential::new(0.5));
+        assert_eq!(None, detector.update(295.0));
+        assert_eq!(None, detector.update(296.0));
+        assert_eq!(Some(295.5), detector.baseline());
+        assert_eq!(Some(PresenceEvent::ObjectAppeared), detector.update(298.0));
+        assert!(detector.is_present());
+        assert_eq!(None, detector.update(302.0));
+        assert_eq!(None, detector.update(f64::NAN));
+        assert_eq!(None, detector.update(f64::INFINITY));
+        assert_eq!(Some(295.5), detector.baseline());
+        assert_eq!(None, detector.update(297.0));
+        assert_eq!(Some(PresenceEvent::ObjectLeft), detector.update(296.9));
+        assert!(!detector.is_present());
+    }
+
+    #[test]
+    fn reports_colder_objects() {
+        let mut detector = PresenceDetector::new(2.0, 0.5, Exponential::new(0.1));
+        detector.update(295.0);
+        assert_eq!(Some(PresenceEvent::ObjectAppeared), detector.update(292.5));
+        detector.reset();
+        assert_eq!(None, detector.baseline());
+        assert!(!detector.is_present());
+    }
+
+    #[test]
+    fn takes_new_baseline_after_timeout() {
+        let mut detector = PresenceDetector::new(2.0, 0.5, Exponential::new(0.5)).with_timeout(3);
+        detector.update(295.0);
+        assert_eq!(Some(PresenceEvent::ObjectAppeared), detector.update(298.0));
+        assert_eq!(None, detector.update(298.1));
+        assert_eq!(None, detector.update(298.2));
+        assert_eq!(Some(PresenceEvent::ObjectLeft), detector.update(298.3));
+        assert_eq!(Some(298.3), detector.baseline());
+        assert_eq!(None, detector.update(298.4));
+        assert!(!detector.is_present());
+    }
+
+    #[test]
+    fn clamps_hysteresis() {
+        let new = |hysteresis| PresenceDetector::new(2.0, hysteresis, Exponential::new(0.5));
+        assert_eq!(1.0, new(3.0).hysteresis());
+        assert_eq!(0.0, new(-1.0).hysteresis());
+        assert_eq!(0.0, new(f64::NAN).hysteresis());
+        let mut detector = new(5.0);
+        detector.update(295.0);
+        assert_eq!(Some(PresenceEvent::ObjectAppeared), detector.update(300.0));
+        assert_eq!(Some(PresenceEvent::ObjectLeft), detector.update(295.5));
+    }
+}
@@ -1,9 +1,13 @@
-use crate::{BitFlagsLow, Error, Register, SensorData, Tmp006};
-use embedded_hal::i2c;
+use crate::{BitFlagsLow, Error, Register, SensorData, Tmp006, DEVICE_ID, MANUFACTURER_ID};
+use embedded_hal::{delay::DelayNs, i2c};
 
-impl<I2C, E> Tmp006<I2C>
+/// Interval between data-ready polls in `measure_once()`.
+const POLL_INTERVAL_MS: u32 = 10;
+
+impl<I2C, D, E> Tmp006<I2C, D>
 where
     I2C: i2c::I2c<Error = E>,
+    D: DelayNs,
 {
     /// Read the object temperature in Kelvins.
     ///
@@ -44,7 +48,7 @@ where
         const V_LSB_SIZE: f64 = 156.25e-9;
 
         let v_obj = f64::from(data.object_voltage) * V_LSB_SIZE;
-        let t_die_k = f64::from(data.ambient_temperature) / 128.0 + 273.15;
+        let t_die_k = data.ambient_temperature_celsius() + 273.15;
 
         let t_diff = t_die_k - T_REF;
         let t_diff_sq = t_diff * t_diff;
@@ -62,21 +66,200 @@ where
     /// [`read_object_temperature()`].
     ///
     /// [`read_object_temperature()`]: struct.Tmp006.html#method.read_object_temperature
+    ///
+    /// If a configuration check interval is set, the configuration is checked
+    /// every that many calls. If it had to be written again, this returns
+    /// `nb::Error::WouldBlock`. See: [`set_config_check_interval()`].
+    ///
+    /// [`set_config_check_interval()`]: struct.Tmp006.html#method.set_config_check_interval
+    ///
+    /// The object voltage and ambient temperature are read in two separate
+    /// transfers. If a conversion finishes in between, they come from
+    /// different conversions. See [`read_sensor_data_coherent()`] for a
+    /// read that detects this.
+    ///
+    /// [`read_sensor_data_coherent()`]: struct.Tmp006.html#method.read_sensor_data_coherent
     pub fn read_sensor_data(&mut self) -> nb::Result<SensorData, Error<E>> {
-        let ready = self.is_data_ready().map_err(nb::Error::Other)?;
-        if !ready {
-            return Err(nb::Error::WouldBlock);
-        }
+        let (v, temp) = self.read_raw_sensor_data()?;
+        Ok(SensorData::from_registers(v, temp))
+    }
+
+    /// Read the raw `V_OBJECT` and `T_AMBIENT` register contents once data is ready.
+    pub(crate) fn read_raw_sensor_data(&mut self) -> nb::Result<(u16, u16), Error<E>> {
+        self.check_data_ready()?;
         let v = self
-            .read_register(Register::V_OBJECT)
+            .read_register(Register::ObjectVoltage)
             .map_err(nb::Error::Other)?;
         let temp = self
-            .read_register(Register::TEMP_AMBIENT)
+            .read_register(Register::AmbientTemperature)
             .map_err(nb::Error::Other)?;
-        let data = SensorData {
-            object_voltage: v as i16,
-            ambient_temperature: temp as i16 / 4,
-        };
+        Ok((v, temp))
+    }
+
+    /// Read the data from the sensor making sure that the object voltage
+    /// and ambient temperature come from the same conversion.
+    ///
+    /// This reads the object voltage, the ambient temperature and then the
+    /// object voltage again. If both object voltage values differ, a
+    /// conversion finished during the read and it is repeated up to
+    /// `retries` times. If the values never match, this returns
+    /// `nb::Error::WouldBlock`.
+    ///
+    /// The registers are updated at the same time at the end of a
+    /// conversion, so matching object voltage values mean that no conversion
+    /// finished in between. The only exception is a new conversion
+    /// producing exactly the same object voltage, in which case the ambient
+    /// temperature is at most one conversion newer.
+    ///
+    /// Otherwise this behaves like [`read_sensor_data()`].
+    ///
+    /// [`read_sensor_data()`]: struct.Tmp006.html#method.read_sensor_data
+    pub fn read_sensor_data_coherent(&mut self, retries: u8) -> nb::Result<SensorData, Error<E>> {
+        self.check_data_ready()?;
+        for _ in 0..=retries {
+            let v = self
+                .read_register(Register::ObjectVoltage)
+                .map_err(nb::Error::Other)?;
+            let temp = self
+                .read_register(Register::AmbientTemperature)
+                .map_err(nb::Error::Other)?;
+            let v_after = self
+                .read_register(Register::ObjectVoltage)
+                .map_err(nb::Error::Other)?;
+            if v == v_after {
+                return Ok(SensorData::from_registers(v, temp));
+            }
+        }
+        Err(nb::Error::WouldBlock)
+    }
+
+    /// Run the configuration check if due and check whether data is ready.
+    fn check_data_ready(&mut self) -> nb::Result<(), Error<E>> {
+        if self.config_check_interval != 0 {
+            self.reads_since_config_check += 1;
+            if self.reads_since_config_check >= self.config_check_interval {
+                self.reads_since_config_check = 0;
+                if self.ensure_configured().map_err(nb::Error::Other)? {
+                    return Err(nb::Error::WouldBlock);
+                }
+            }
+        }
+        let ready = self.is_data_ready().map_err(nb::Error::Other)?;
+        if !ready {
+            return Err(nb::Error::WouldBlock);
+        }
+        Ok(())
+    }
+
+    /// Read the data from a conversion that has not been read before.
+    ///
+    /// Reading the result registers does not clear the data-ready bit, so
+    /// [`read_sensor_data()`] returns the same conversion again if it is
+    /// called faster than the conversion rate. This takes the current time in
+    /// milliseconds from a monotonic clock and returns `nb::Error::WouldBlock`
+    /// without accessing the device until one conversion period has passed
+    /// since the previous reading. Nothing is written to the device, so the
+    /// ongoing conversion is not disturbed.
+    ///
+    /// The readings are expected one conversion period apart starting from
+    /// the first one, so polling late does not delay the following ones. If
+    /// a whole period is missed, the schedule starts again at the reading.
+    /// The nominal conversion period of the driver configuration is used.
+    /// See: [`load_config()`] if the device was configured beforehand.
+    ///
+    /// Writing the configuration clears the data-ready bit and starts the
+    /// schedule again at the next reading.
+    ///
+    /// This is meant to be polled faster than the conversion rate. When
+    /// polling once per conversion, for example on the DRDY pin, use
+    /// [`Sampler`] instead.
+    ///
+    /// [`read_sensor_data()`]: struct.Tmp006.html#method.read_sensor_data
+    /// [`Sampler`]: struct.Sampler.html
+    /// [`load_config()`]: struct.Tmp006.html#method.load_config
+    pub fn read_fresh_sensor_data(&mut self, now_ms: u64) -> nb::Result<SensorData, Error<E>> {
+        if self.next_fresh_ms.is_some_and(|due| now_ms < due) {
+            return Err(nb::Error::WouldBlock);
+        }
+        let data = self.read_sensor_data()?;
+        let period = u64::from(self.config.conversion_rate().period_ms());
+        self.next_fresh_ms = Some(match self.next_fresh_ms {
+            Some(due) if now_ms < due + period => due + period,
+            _ => now_ms + period,
+        });
+        self.fresh_read_count = self.fresh_read_count.wrapping_add(1);
+        Ok(data)
+    }
+
+    /// Number of readings returned by [`read_fresh_sensor_data()`].
+    ///
+    /// Each of them comes from a different conversion. Conversions that were
+    /// never read are not counted. This wraps around on overflow.
+    ///
+    /// [`read_fresh_sensor_data()`]: struct.Tmp006.html#method.read_fresh_sensor_data
+    pub fn fresh_read_count(&self) -> u32 {
+        self.fresh_read_count
+    }
+
+    /// Read the ambient (die) temperature in °C.
+    ///
+    /// This does not check whether data is ready, so it returns the result
+    /// of the last conversion, which may have been read before.
+    pub fn read_ambient_temperature(&mut self) -> Result<f64, Error<E>> {
+        let temp = self.read_register(Register::AmbientTemperature)?;
+        Ok(SensorData::from_registers(0, temp).ambient_temperature_celsius())
+    }
+
+    /// Read the object voltage: `[-32768..32767]` in 156.25 nV units.
+    ///
+    /// This does not check whether data is ready, so it returns the result
+    /// of the last conversion, which may have been read before.
+    pub fn read_object_voltage(&mut self) -> Result<i16, Error<E>> {
+        let v = self.read_register(Register::ObjectVoltage)?;
+        Ok(v as i16)
+    }
+
+    /// Perform a single measurement and power the sensor down afterwards.
+    ///
+    /// The TMP006 has no hardware one-shot mode, so this enables the sensor,
+    /// waits for a conversion to complete at the configured conversion rate,
+    /// reads the data and disables the sensor again. Enabling the sensor
+    /// clears the data-ready bit, so any stale result is discarded.
+    ///
+    /// The sensor draws typically 240 µA while converting and 0.5 µA in
+    /// power-down, which results in the following charge per measurement:
+    ///
+    /// | Conversion rate | Conversion time | Charge |
+    /// |-----------------|-----------------|--------|
+    /// | `Cps4`          | 0.25 s          | 60 µC  |
+    /// | `Cps2`          | 0.5 s           | 120 µC |
+    /// | `Cps1`          | 1 s             | 240 µC |
+    /// | `Cps0_5`        | 2 s             | 480 µC |
+    /// | `Cps0_25`       | 4 s             | 960 µC |
+    ///
+    /// Returns `Error::Timeout` if no data is ready after twice the
+    /// conversion time. The sensor is disabled in any case.
+    pub fn measure_once<DM: DelayNs>(&mut self, delay: &mut DM) -> Result<SensorData, Error<E>> {
+        self.enable()?;
+        let period_ms = self.config.conversion_rate().period_ms();
+        delay.delay_ms(period_ms);
+        let mut result = Err(Error::Timeout);
+        for _ in 0..=period_ms / POLL_INTERVAL_MS {
+            match self.read_sensor_data() {
+                Ok(data) => {
+                    result = Ok(data);
+                    break;
+                }
+                Err(nb::Error::Other(e)) => {
+                    result = Err(e);
+                    break;
+                }
+                Err(nb::Error::WouldBlock) => delay.delay_ms(POLL_INTERVAL_MS),
+            }
+        }
+        let disabled = self.disable();
+        let data = result?;
+        disabled?;
         Ok(data)
     }
 
@@ -85,7 +268,7 @@ where
     /// When this returens `false` it means that a conversion is in progress.
     #[allow(clippy::wrong_self_convention)]
     pub fn is_data_ready(&mut self) -> Result<bool, Error<E>> {
-        let config = self.read_register(Register::CONFIG)?;
+        let config = self.read_register(Register::Config)?;
         Ok((config & u16::from(BitFlagsLow::DRDY)) != 0)
     }
 
@@ -93,21 +276,44 @@ where
     ///
     /// This is per default `0x5449`.
     pub fn read_manufacturer_id(&mut self) -> Result<u16, Error<E>> {
-        self.read_register(Register::MANUFAC_ID)
+        self.read_register(Register::ManufacturerId)
     }
 
     /// Read the device ID.
     ///
     /// This is per default `0x0067`.
     pub fn read_device_id(&mut self) -> Result<u16, Error<E>> {
-        self.read_register(Register::DEVICE_ID)
+        self.read_register(Register::DeviceId)
+    }
+
+    /// Check that the device identifies itself as a TMP006.
+    ///
+    /// Returns `Error::InvalidDeviceId` if the manufacturer ID is not
+    /// `0x5449` or the device ID is not `0x0067`.
+    pub fn check_device_id(&mut self) -> Result<(), Error<E>> {
+        let manufacturer_id = self.read_manufacturer_id()?;
+        let device_id = self.read_device_id()?;
+        if manufacturer_id == MANUFACTURER_ID && device_id == DEVICE_ID {
+            Ok(())
+        } else {
+            Err(Error::InvalidDeviceId {
+                manufacturer_id,
+                device_id,
+            })
+        }
     }
 
-    fn read_register(&mut self, register: u8) -> Result<u16, Error<E>> {
+    pub(crate) fn read_register(&mut self, register: Register) -> Result<u16, Error<E>> {
         let mut data = [0; 2];
-        self.i2c
-            .write_read(self.address, &[register], &mut data)
-            .map_err(Error::I2C)?;
-        Ok((u16::from(data[0]) << 8) | u16::from(data[1]))
+        self.transfer(|i2c, address| i2c.write_read(address, &[register.address()], &mut data))?;
+        let value = (u16::from(data[0]) << 8) | u16::from(data[1]);
+        debug!(
+            "tmp006 0x{:02X}: read {:?} (0x{:02X}): 0x{:04X}",
+            self.address,
+            register,
+            register.address(),
+            value
+        );
+        Ok(value)
     }
 }
@@ -0,0 +1,49 @@
+//! Raw register access
+
+use crate::{BitFlagsHigh, ConfigHigh, Error, Register, Tmp006};
+use embedded_hal::{delay::DelayNs, i2c};
+
+impl<I2C, D, E> Tmp006<I2C, D>
+where
+    I2C: i2c::I2c<Error = E>,
+    D: DelayNs,
+{
+    /// Read a register.
+    ///
+    /// No data-ready check is done before reading the result registers.
+    pub fn read_raw_register(&mut self, register: Register) -> Result<u16, Error<E>> {
+        self.read_register(register)
+    }
+
+    /// Write a register.
+    ///
+    /// Only the configuration register is writable. Writing it updates the
+    /// driver configuration with the most significant byte. The least
+    /// significant byte is read-only, so it is written as zero.
+    ///
+    /// Note: writing the configuration clears the data-ready bit.
+    pub fn write_raw_register(&mut self, register: Register, value: u16) -> Result<(), Error<E>> {
+        let [msb, lsb] = value.to_be_bytes();
+        if register == Register::Config {
+            self.write_config(ConfigHigh { bits: msb })
+        } else {
+            self.transfer(|i2c, address| i2c.write(address, &[register.address(), msb, lsb]))?;
+            debug!(
+                "tmp006 0x{:02X}: wrote {:?} (0x{:02X}): 0x{:04X}",
+                self.address,
+                register,
+                register.address(),
+                value
+            );
+            Ok(())
+        }
+    }
+
+    /// Most significant byte of the configuration register as stored in
+    /// the driver.
+    ///
+    /// See [`BitFlagsHigh`](struct.BitFlagsHigh.html) for its contents.
+    pub fn config_register_msb(&self) -> u8 {
+        self.config.bits & !BitFlagsHigh::SW_RESET
+    }
+}
@@ -0,0 +1,165 @@
+//! I²C retry policy
+
+use crate::{Error, Tmp006};
+use embedded_hal::{
+    delay::DelayNs,
+    i2c::{self, Error as _, ErrorKind},
+};
+
+/// I²C retry policy for transient bus errors.
+///
+/// Before each retry the driver waits for the backoff delay, which doubles
+/// with every further retry of the same transfer.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct RetryPolicy {
+    /// Maximum number of retries of a failed transfer.
+    pub retries: u8,
+    /// Delay before the first retry in microseconds.
+    pub backoff_us: u32,
+    /// Retry when the address or data was not acknowledged.
+    pub no_acknowledge: bool,
+    /// Retry when the arbitration was lost.
+    pub arbitration_loss: bool,
+    /// Retry on a data overrun.
+    pub overrun: bool,
+    /// Retry on a bus error (misplaced START or STOP condition).
+    pub bus: bool,
+    /// Retry on any other error.
+    pub other: bool,
+}
+
+impl RetryPolicy {
+    /// Create new retry policy for not-acknowledged transfers and
+    /// arbitration loss.
+    pub fn new(retries: u8, backoff_us: u32) -> Self {
+        RetryPolicy {
+            retries,
+            backoff_us,
+            no_acknowledge: true,
+            arbitration_loss: true,
+            ..Default::default()
+        }
+    }
+
+    /// Whether a transfer failing with the given error kind is retried.
+    pub fn is_retryable(&self, kind: ErrorKind) -> bool {
+        match kind {
+            ErrorKind::NoAcknowledge(_) => self.no_acknowledge,
+            ErrorKind::ArbitrationLoss => self.arbitration_loss,
+            ErrorKind::Overrun => self.overrun,
+            ErrorKind::Bus => self.bus,
+            _ => self.other,
+        }
+    }
+}
+
+/// Delay doing nothing, used when no retry policy is set.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct NoDelay;
+
+impl DelayNs for NoDelay {
+    fn delay_ns(&mut self, _ns: u32) {}
+}
+
+impl<I2C, D, E> Tmp006<I2C, D>
+where
+    I2C: i2c::I2c<Error = E>,
+    D: DelayNs,
+{
+    /// Set the retry policy for transient I²C bus errors.
+    ///
+    /// The delay is used to wait before each retry.
+    pub fn with_retry_policy<D2: DelayNs>(self, policy: RetryPolicy, delay: D2) -> Tmp006<I2C, D2> {
+        Tmp006 {
+            i2c: self.i2c,
+            delay,
+            retry_policy: policy,
+            address: self.address,
+            config: self.config,
+            config_check_interval: self.config_check_interval,
+            reads_since_config_check: self.reads_since_config_check,
+            reconfiguration_count: self.reconfiguration_count,
+            verify_writes: self.verify_writes,
+            error_count: self.error_count,
+            retry_count: self.retry_count,
+            next_fresh_ms: self.next_fresh_ms,
+            fresh_read_count: self.fresh_read_count,
+        }
+    }
+
+    /// Retry policy.
+    pub fn retry_policy(&self) -> RetryPolicy {
+        self.retry_policy
+    }
+
+    /// Number of failed I²C transfers, including the ones that were retried.
+    pub fn error_count(&self) -> u32 {
+        self.error_count
+    }
+
+    /// Number of retried I²C transfers.
+    pub fn retry_count(&self) -> u32 {
+        self.retry_count
+    }
+
+    /// Run an I²C transfer according to the retry policy.
+    pub(crate) fn transfer<T>(
+        &mut self,
+        mut f: impl FnMut(&mut I2C, u8) -> Result<T, E>,
+    ) -> Result<T, Error<E>> {
+        let mut attempt = 0;
+        loop {
+            match f(&mut self.i2c, self.address) {
+                Ok(value) => return Ok(value),
+                Err(e) => {
+                    self.error_count = self.error_count.saturating_add(1);
+                    let kind = error_kind::<I2C>(&e);
+                    warn!(
+                        "tmp006 0x{:02X}: I2C transfer failed: {:?} (attempt {})",
+                        self.address,
+                        kind,
+                        attempt + 1
+                    );
+                    let policy = &self.retry_policy;
+                    if attempt >= policy.retries || !policy.is_retryable(kind) {
+                        return Err(Error::I2C(e));
+                    }
+                    let backoff = policy.backoff_us.saturating_mul(1 << attempt.min(31));
+                    self.delay.delay_us(backoff);
+                    attempt += 1;
+                    self.retry_count = self.retry_count.saturating_add(1);
+                }
+            }
+        }
+    }
+}
+
+fn error_kind<I2C: i2c::I2c>(error: &I2C::Error) -> ErrorKind {
+    error.kind()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use embedded_hal::i2c::NoAcknowledgeSource;
+
+    #[test]
+    fn default_policy_does_not_retry() {
+        let policy = RetryPolicy::default();
+        assert_eq!(0, policy.retries);
+        assert!(!policy.is_retryable(ErrorKind::ArbitrationLoss));
+    }
+
+    #[test]
+    fn retries_transient_errors_only() {
+        let policy = RetryPolicy::new(3, 100);
+        let nack = ErrorKind::NoAcknowledge(NoAcknowledgeSource::Address);
+        assert!(policy.is_retryable(nack));
+        assert!(policy.is_retryable(ErrorKind::ArbitrationLoss));
+        assert!(!policy.is_retryable(ErrorKind::Bus));
+        assert!(!policy.is_retryable(ErrorKind::Overrun));
+        assert!(!policy.is_retryable(ErrorKind::Other));
+    }
+}
@@ -0,0 +1,122 @@
+//! Timestamped sampling
+
+use crate::{Error, NoDelay, SensorData, Tmp006};
+use embedded_hal::{delay::DelayNs, i2c};
+
+/// Monotonic time source.
+///
+/// This is implemented for closures returning the current time.
+pub trait Clock {
+    /// Current time in milliseconds.
+    fn now_ms(&mut self) -> u64;
+}
+
+impl<F> Clock for F
+where
+    F: FnMut() -> u64,
+{
+    fn now_ms(&mut self) -> u64 {
+        self()
+    }
+}
+
+/// Timestamped sensor data.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct Sample {
+    /// Time at which the data was read in milliseconds.
+    pub timestamp: u64,
+    /// Conversion sequence number, including missed conversions.
+    pub sequence: u32,
+    /// Number of conversions missed since the previous sample.
+    pub missed: u32,
+    /// Sensor data.
+    pub data: SensorData,
+}
+
+/// Timestamped streaming sampler.
+///
+/// This wraps the device driver and a monotonic time source and detects
+/// skipped conversions when more than one conversion period has passed
+/// between two samples. Skipped conversions are reported as overruns.
+///
+/// This is meant to be polled once per conversion, for example when the
+/// DRDY pin asserts or from a timer running at the conversion rate.
+/// Reads less than half a conversion period after the previous sample are
+/// considered to be the same conversion and return `nb::Error::WouldBlock`.
+#[derive(Debug)]
+pub struct Sampler<I2C, C, D = NoDelay> {
+    sensor: Tmp006<I2C, D>,
+    clock: C,
+    last_timestamp: Option<u64>,
+    sequence: u32,
+    overruns: u32,
+}
+
+impl<I2C, C, D, E> Sampler<I2C, C, D>
+where
+    I2C: i2c::I2c<Error = E>,
+    D: DelayNs,
+    C: Clock,
+{
+    /// Create new sampler.
+    pub fn new(sensor: Tmp006<I2C, D>, clock: C) -> Self {
+        Sampler {
+            sensor,
+            clock,
+            last_timestamp: None,
+            sequence: 0,
+            overruns: 0,
+        }
+    }
+
+    /// Destroy sampler, return device driver and time source.
+    pub fn destroy(self) -> (Tmp006<I2C, D>, C) {
+        (self.sensor, self.clock)
+    }
+
+    /// Access the device driver, for example to change its configuration.
+    pub fn sensor(&mut self) -> &mut Tmp006<I2C, D> {
+        &mut self.sensor
+    }
+
+    /// Total number of missed conversions.
+    pub fn overruns(&self) -> u32 {
+        self.overruns
+    }
+
+    /// Read the next timestamped sample.
+    ///
+    /// Returns `nb::Error::WouldBlock` until data is ready and at least half
+    /// a conversion period has passed since the previous sample.
+    pub fn read(&mut self) -> nb::Result<Sample, Error<E>> {
+        let timestamp = self.clock.now_ms();
+        let period = u64::from(self.sensor.config.conversion_rate().period_ms());
+        let elapsed = self
+            .last_timestamp
+            .map(|last| timestamp.saturating_sub(last));
+        if elapsed.is_some_and(|elapsed| elapsed < period / 2) {
+            return Err(nb::Error::WouldBlock);
+        }
+        let data = self.sensor.read_sensor_data()?;
+        let missed = match elapsed {
+            Some(elapsed) => {
+                let conversions = (elapsed + period / 2) / period;
+                conversions.saturating_sub(1).min(u64::from(u32::MAX)) as u32
+            }
+            None => 0,
+        };
+        if self.last_timestamp.is_some() {
+            self.sequence = self.sequence.wrapping_add(1).wrapping_add(missed);
+        }
+        self.last_timestamp = Some(timestamp);
+        self.overruns = self.overruns.saturating_add(missed);
+        Ok(Sample {
+            timestamp,
+            sequence: self.sequence,
+            missed,
+            data,
+        })
+    }
+}
@@ -0,0 +1,170 @@
+//! Duty-cycle scheduling
+
+use crate::types::{ALL_CONVERSION_RATES, POWER_DOWN_SUPPLY_CURRENT_UA};
+use crate::{ConversionRate, Error, SensorData, Tmp006};
+use embedded_hal::{delay::DelayNs, i2c};
+
+/// Duty-cycle scheduler for periodic measurements.
+///
+/// For each sample the sensor is enabled for a single conversion and
+/// powered down for the rest of the sampling interval.
+/// See: [`measure_once()`].
+///
+/// The estimations are based on the typical supply currents from the
+/// datasheet: 240 µA while converting and 0.5 µA in power-down.
+///
+/// [`measure_once()`]: struct.Tmp006.html#method.measure_once
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(try_from = "SchedulerParams"))]
+pub struct Scheduler {
+    conversion_rate: ConversionRate,
+    interval_ms: u32,
+}
+
+/// Unvalidated scheduler parameters, checked when deserializing.
+#[cfg(feature = "serde")]
+#[derive(serde::Deserialize)]
+struct SchedulerParams {
+    conversion_rate: ConversionRate,
+    interval_ms: u32,
+}
+
+#[cfg(feature = "serde")]
+impl TryFrom<SchedulerParams> for Scheduler {
+    type Error = &'static str;
+
+    fn try_from(params: SchedulerParams) -> Result<Self, Self::Error> {
+        Self::with_conversion_rate(params.conversion_rate, params.interval_ms)
+            .ok_or("the conversion does not fit into the sampling interval")
+    }
+}
+
+impl Scheduler {
+    /// Plan a duty cycle for the target sampling interval in milliseconds
+    /// and average current budget in µA.
+    ///
+    /// This selects the conversion rate with the most averaging (lowest
+    /// noise) whose conversion fits into the sampling interval and whose
+    /// estimated average current is within the budget.
+    ///
+    /// Returns `None` if no conversion rate satisfies both constraints.
+    pub fn new(interval_ms: u32, current_budget_ua: f64) -> Option<Self> {
+        ALL_CONVERSION_RATES
+            .iter()
+            .rev()
+            .filter_map(|&rate| Self::with_conversion_rate(rate, interval_ms))
+            .find(|scheduler| scheduler.average_current_ua() <= current_budget_ua)
+    }
+
+    /// Create a duty cycle with a fixed conversion rate and sampling interval
+    /// in milliseconds.
+    ///
+    /// Returns `None` if the conversion does not fit into the interval.
+    pub fn with_conversion_rate(conversion_rate: ConversionRate, interval_ms: u32) -> Option<Self> {
+        if conversion_rate.period_ms() <= interval_ms {
+            Some(Scheduler {
+                conversion_rate,
+                interval_ms,
+            })
+        } else {
+            None
+        }
+    }
+
+    /// Conversion rate used for each sample.
+    pub fn conversion_rate(&self) -> ConversionRate {
+        self.conversion_rate
+    }
+
+    /// Sampling interval in milliseconds.
+    pub fn interval_ms(&self) -> u32 {
+        self.interval_ms
+    }
+
+    /// Fraction of the time the sensor is enabled: `[0.0..1.0]`
+    pub fn duty_cycle(&self) -> f64 {
+        f64::from(self.conversion_rate.period_ms()) / f64::from(self.interval_ms)
+    }
+
+    /// Estimated average supply current in µA.
+    pub fn average_current_ua(&self) -> f64 {
+        let duty_cycle = self.duty_cycle();
+        self.conversion_rate.supply_current_ua() * duty_cycle
+            + POWER_DOWN_SUPPLY_CURRENT_UA * (1.0 - duty_cycle)
+    }
+
+    /// Number of samples taken per hour.
+    pub fn samples_per_hour(&self) -> f64 {
+        3_600_000.0 / f64::from(self.interval_ms)
+    }
+
+    /// Take a sample and wait until the next one is due.
+    ///
+    /// This sets the conversion rate if needed, performs a single
+    /// measurement and then waits for the rest of the sampling interval
+    /// with the sensor powered down. The time spent communicating with the
+    /// sensor is not accounted for, so the actual interval is slightly longer.
+    pub fn sample<I2C, D, E, DM>(
+        &self,
+        sensor: &mut Tmp006<I2C, D>,
+        delay: &mut DM,
+    ) -> Result<SensorData, Error<E>>
+    where
+        I2C: i2c::I2c<Error = E>,
+        D: DelayNs,
+        DM: DelayNs,
+    {
+        if sensor.config.conversion_rate() != self.conversion_rate {
+            sensor.set_conversion_rate(self.conversion_rate)?;
+        }
+        let data = sensor.measure_once(delay)?;
+        delay.delay_ms(
+            self.interval_ms
+                .saturating_sub(self.conversion_rate.period_ms()),
+        );
+        Ok(data)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn selects_most_averaging_within_budget() {
+        let scheduler = Scheduler::new(60_000, 20.0).unwrap();
+        assert_eq!(ConversionRate::Cps0_25, scheduler.conversion_rate());
+        assert_eq!(60.0, scheduler.samples_per_hour());
+
+        let scheduler = Scheduler::new(60_000, 5.0).unwrap();
+        assert_eq!(ConversionRate::Cps1, scheduler.conversion_rate());
+        assert!(scheduler.average_current_ua() <= 5.0);
+    }
+
+    #[test]
+    fn selects_conversion_fitting_interval() {
+        let scheduler = Scheduler::new(500, 1000.0).unwrap();
+        assert_eq!(ConversionRate::Cps2, scheduler.conversion_rate());
+        assert_eq!(1.0, scheduler.duty_cycle());
+        assert_eq!(240.0, scheduler.average_current_ua());
+    }
+
+    #[test]
+    fn cannot_plan_short_interval() {
+        assert_eq!(None, Scheduler::new(200, 1000.0));
+    }
+
+    #[test]
+    fn cannot_plan_low_budget() {
+        assert_eq!(None, Scheduler::new(1000, 50.0));
+    }
+
+    #[test]
+    fn can_estimate_average_current() {
+        let scheduler = Scheduler::with_conversion_rate(ConversionRate::Cps4, 1000).unwrap();
+        assert_eq!(0.25, scheduler.duty_cycle());
+        assert_eq!(60.375, scheduler.average_current_ua());
+        assert_eq!(3600.0, scheduler.samples_per_hour());
+    }
+}
@@ -0,0 +1,153 @@
+//! Thermal settling detection
+
+use crate::{ConversionRate, DataStatistics, Error, SensorData, Tmp006};
+use embedded_hal::{delay::DelayNs, i2c};
+
+/// Thermal settling state.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum SettlingState {
+    /// The readings are still biased (default)
+    #[default]
+    Settling,
+    /// The readings are stable
+    Stable,
+}
+
+/// Thermal settling detection after enabling or resetting the sensor.
+///
+/// After power-up, [`enable()`] or [`reset()`] the die temperature and offset
+/// settle over several seconds. The sensor is considered stable once the
+/// last `N` readings show an ambient temperature slope and an object voltage
+/// standard deviation within the given limits. The stable state is kept
+/// until [`restart()`] is called.
+///
+/// [`enable()`]: struct.Tmp006.html#method.enable
+/// [`reset()`]: struct.Tmp006.html#method.reset
+/// [`restart()`]: struct.SettlingDetector.html#method.restart
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SettlingDetector<const N: usize> {
+    statistics: DataStatistics<N>,
+    conversion_rate: ConversionRate,
+    max_ambient_slope: f64,
+    max_voltage_std_dev: f64,
+    state: SettlingState,
+}
+
+impl<const N: usize> SettlingDetector<N> {
+    /// Create new settling detector for readings taken at the given
+    /// conversion rate.
+    ///
+    /// The maximum ambient temperature slope is given in °C/s and the
+    /// maximum object voltage standard deviation in LSB.
+    pub fn new(
+        conversion_rate: ConversionRate,
+        max_ambient_slope: f64,
+        max_voltage_std_dev: f64,
+    ) -> Self {
+        SettlingDetector {
+            statistics: DataStatistics::new(),
+            conversion_rate,
+            max_ambient_slope,
+            max_voltage_std_dev,
+            state: SettlingState::Settling,
+        }
+    }
+
+    /// Current state.
+    pub fn state(&self) -> SettlingState {
+        self.state
+    }
+
+    /// Statistics of the last `N` readings.
+    pub fn statistics(&self) -> &DataStatistics<N> {
+        &self.statistics
+    }
+
+    /// Evaluate new sensor data.
+    pub fn update(&mut self, data: SensorData) -> SettlingState {
+        self.statistics.push(data);
+        if self.state == SettlingState::Settling && self.is_settled() {
+            self.state = SettlingState::Stable;
+        }
+        self.state
+    }
+
+    /// Start over, for example after enabling or resetting the sensor.
+    pub fn restart(&mut self) {
+        self.statistics.clear();
+        self.state = SettlingState::Settling;
+    }
+
+    /// Read the sensor data, suppressing it until the sensor is stable.
+    ///
+    /// Returns `nb::Error::WouldBlock` while a conversion is in progress or
+    /// the sensor is still settling.
+    pub fn read_when_stable<I2C, D, E>(
+        &mut self,
+        sensor: &mut Tmp006<I2C, D>,
+    ) -> nb::Result<SensorData, Error<E>>
+    where
+        I2C: i2c::I2c<Error = E>,
+        D: DelayNs,
+    {
+        let data = sensor.read_sensor_data()?;
+        match self.update(data) {
+            SettlingState::Stable => Ok(data),
+            SettlingState::Settling => Err(nb::Error::WouldBlock),
+        }
+    }
+
+    fn is_settled(&self) -> bool {
+        let ambient_slope = self.statistics.ambient_drift_rate(self.conversion_rate);
+        let voltage_std_dev = self.statistics.object_voltage().std_dev();
+        match (ambient_slope, voltage_std_dev) {
+            (Some(slope), Some(std_dev)) => {
+                self.statistics.object_voltage().is_full()
+                    && libm::fabs(slope) <= self.max_ambient_slope
+                    && std_dev <= self.max_voltage_std_dev
+            }
+            _ => false,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn data(object_voltage: i16, ambient_temperature: i16) -> SensorData {
+        SensorData {
+            object_voltage,
+            ambient_temperature,
+        }
+    }
+
+    #[test]
+    fn settles_once_window_is_stable() {
+        let mut detector = SettlingDetector::<4>::new(ConversionRate::Cps1, 0.02, 2.0);
+        // ambient temperature rising 1/16 °C/s
+        for i in 0..6 {
+            assert_eq!(
+                SettlingState::Settling,
+                detector.update(data(0, 800 + 2 * i))
+            );
+        }
+        assert_eq!(SettlingState::Settling, detector.update(data(0, 810)));
+        assert_eq!(SettlingState::Stable, detector.update(data(1, 810)));
+        assert_eq!(SettlingState::Stable, detector.update(data(100, 900)));
+        detector.restart();
+        assert_eq!(SettlingState::Settling, detector.state());
+    }
+
+    #[test]
+    fn noisy_voltage_is_not_stable() {
+        let mut detector = SettlingDetector::<4>::new(ConversionRate::Cps1, 0.05, 2.0);
+        for i in 0..8 {
+            let voltage = if i % 2 == 0 { 10 } else { -10 };
+            assert_eq!(SettlingState::Settling, detector.update(data(voltage, 800)));
+        }
+    }
+}
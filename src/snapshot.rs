@@ -0,0 +1,169 @@
+//! Register snapshot for debugging
+
+use crate::{
+    BitFlagsHigh, BitFlagsLow, ConfigHigh, ConversionRate, Error, Register, SensorData, Tmp006,
+};
+use core::fmt;
+use embedded_hal::{delay::DelayNs, i2c};
+
+/// Object voltage LSB size in µV.
+const V_LSB_SIZE_UV: f64 = 0.15625;
+
+/// Raw contents of all the documented registers.
+///
+/// The [`Display`](https://doc.rust-lang.org/core/fmt/trait.Display.html)
+/// implementation decodes every field, which is useful for bug reports.
+/// See: [`dump_registers()`](struct.Tmp006.html#method.dump_registers).
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct RegisterSnapshot {
+    /// `V_OBJECT` register contents.
+    pub object_voltage: u16,
+    /// `T_AMBIENT` register contents.
+    pub ambient_temperature: u16,
+    /// Configuration register contents.
+    pub config: u16,
+    /// Manufacturer ID register contents.
+    pub manufacturer_id: u16,
+    /// Device ID register contents.
+    pub device_id: u16,
+}
+
+impl RegisterSnapshot {
+    /// Sensor data decoded from the result registers.
+    pub fn sensor_data(&self) -> SensorData {
+        SensorData::from_registers(self.object_voltage, self.ambient_temperature)
+    }
+
+    /// Object voltage in µV.
+    pub fn object_voltage_uv(&self) -> f64 {
+        f64::from(self.sensor_data().object_voltage) * V_LSB_SIZE_UV
+    }
+
+    /// Ambient temperature in °C.
+    pub fn ambient_temperature_celsius(&self) -> f64 {
+        self.sensor_data().ambient_temperature_celsius()
+    }
+
+    /// Whether the sensor is in continuous conversion mode.
+    pub fn is_enabled(&self) -> bool {
+        (self.config_high() & BitFlagsHigh::MOD) != 0
+    }
+
+    /// Conversion rate.
+    pub fn conversion_rate(&self) -> ConversionRate {
+        ConfigHigh {
+            bits: self.config_high(),
+        }
+        .conversion_rate()
+    }
+
+    /// Whether the DRDY pin is enabled.
+    pub fn is_drdy_pin_enabled(&self) -> bool {
+        (self.config_high() & BitFlagsHigh::DRDY_EN) != 0
+    }
+
+    /// Whether data is ready to be read.
+    pub fn is_data_ready(&self) -> bool {
+        (self.config & u16::from(BitFlagsLow::DRDY)) != 0
+    }
+
+    fn config_high(&self) -> u8 {
+        (self.config >> 8) as u8
+    }
+}
+
+impl fmt::Display for RegisterSnapshot {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mode = if self.is_enabled() {
+            "continuous"
+        } else {
+            "power-down"
+        };
+        let rate = match self.conversion_rate() {
+            ConversionRate::Cps4 => "4",
+            ConversionRate::Cps2 => "2",
+            ConversionRate::Cps1 => "1",
+            ConversionRate::Cps0_5 => "0.5",
+            ConversionRate::Cps0_25 => "0.25",
+        };
+        writeln!(
+            f,
+            "V_OBJECT  (0x{:02X}): 0x{:04X} ({} µV)",
+            Register::ObjectVoltage.address(),
+            self.object_voltage,
+            self.object_voltage_uv()
+        )?;
+        writeln!(
+            f,
+            "T_AMBIENT (0x{:02X}): 0x{:04X} ({} °C)",
+            Register::AmbientTemperature.address(),
+            self.ambient_temperature,
+            self.ambient_temperature_celsius()
+        )?;
+        writeln!(
+            f,
+            "CONFIG    (0x{:02X}): 0x{:04X} (mode: {}, rate: {} conversions/s, DRDY_EN: {}, DRDY: {})",
+            Register::Config.address(),
+            self.config,
+            mode,
+            rate,
+            u8::from(self.is_drdy_pin_enabled()),
+            u8::from(self.is_data_ready())
+        )?;
+        writeln!(
+            f,
+            "MANUF_ID  (0x{:02X}): 0x{:04X}",
+            Register::ManufacturerId.address(),
+            self.manufacturer_id
+        )?;
+        write!(
+            f,
+            "DEVICE_ID (0x{:02X}): 0x{:04X}",
+            Register::DeviceId.address(),
+            self.device_id
+        )
+    }
+}
+
+impl<I2C, D, E> Tmp006<I2C, D>
+where
+    I2C: i2c::I2c<Error = E>,
+    D: DelayNs,
+{
+    /// Read all the documented registers.
+    ///
+    /// The result registers are read without checking whether data is ready.
+    pub fn dump_registers(&mut self) -> Result<RegisterSnapshot, Error<E>> {
+        Ok(RegisterSnapshot {
+            object_voltage: self.read_register(Register::ObjectVoltage)?,
+            ambient_temperature: self.read_register(Register::AmbientTemperature)?,
+            config: self.read_register(Register::Config)?,
+            manufacturer_id: self.read_register(Register::ManufacturerId)?,
+            device_id: self.read_register(Register::DeviceId)?,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn can_decode_snapshot() {
+        let snapshot = RegisterSnapshot {
+            object_voltage: 0xFF9C,
+            ambient_temperature: 0x0A8C,
+            config: 0x7580,
+            manufacturer_id: 0x5449,
+            device_id: 0x0067,
+        };
+        assert_eq!(-15.625, snapshot.object_voltage_uv());
+        assert_eq!(21.09375, snapshot.ambient_temperature_celsius());
+        assert!(snapshot.is_enabled());
+        assert_eq!(ConversionRate::Cps1, snapshot.conversion_rate());
+        assert!(snapshot.is_drdy_pin_enabled());
+        assert!(snapshot.is_data_ready());
+    }
+}
@@ -0,0 +1,217 @@
+//! Rolling statistics
+
+use crate::window::Window;
+use crate::{ConversionRate, SensorData};
+
+/// Rolling statistics over the last `N` values.
+///
+/// Non-finite values (`NaN` and infinities) are ignored.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(transparent))]
+pub struct Statistics<const N: usize> {
+    window: Window<N>,
+}
+
+impl<const N: usize> Statistics<N> {
+    /// Create new empty statistics.
+    pub const fn new() -> Self {
+        Statistics {
+            window: Window::new(),
+        }
+    }
+
+    /// Add a value, replacing the oldest one if the window is full.
+    pub fn push(&mut self, value: f64) {
+        if value.is_finite() {
+            self.window.push(value);
+        }
+    }
+
+    /// Number of values in the window.
+    pub fn len(&self) -> usize {
+        self.window.len()
+    }
+
+    /// Whether the window is empty.
+    pub fn is_empty(&self) -> bool {
+        self.window.len() == 0
+    }
+
+    /// Whether the window contains `N` values.
+    pub fn is_full(&self) -> bool {
+        self.window.is_full()
+    }
+
+    /// Remove all values.
+    pub fn clear(&mut self) {
+        self.window.clear();
+    }
+
+    /// Minimum value.
+    pub fn min(&self) -> Option<f64> {
+        self.window.iter().reduce(f64::min)
+    }
+
+    /// Maximum value.
+    pub fn max(&self) -> Option<f64> {
+        self.window.iter().reduce(f64::max)
+    }
+
+    /// Mean value.
+    pub fn mean(&self) -> Option<f64> {
+        if self.is_empty() {
+            None
+        } else {
+            Some(self.window.iter().sum::<f64>() / self.len() as f64)
+        }
+    }
+
+    /// Population standard deviation.
+    pub fn std_dev(&self) -> Option<f64> {
+        let mean = self.mean()?;
+        let sum_sq: f64 = self.window.iter().map(|v| (v - mean) * (v - mean)).sum();
+        Some(libm::sqrt(sum_sq / self.len() as f64))
+    }
+
+    /// Least-squares slope per value.
+    ///
+    /// Returns `None` if there are less than two values.
+    pub fn slope(&self) -> Option<f64> {
+        let n = self.len();
+        if n < 2 {
+            return None;
+        }
+        let mean = self.mean()?;
+        let mean_x = (n - 1) as f64 / 2.0;
+        let (mut sum_xy, mut sum_xx) = (0.0, 0.0);
+        for (x, y) in self.window.iter().enumerate() {
+            let dx = x as f64 - mean_x;
+            sum_xy += dx * (y - mean);
+            sum_xx += dx * dx;
+        }
+        Some(sum_xy / sum_xx)
+    }
+
+    /// Least-squares slope per second for values read at the given
+    /// conversion rate.
+    pub fn slope_per_second(&self, rate: ConversionRate) -> Option<f64> {
+        Some(self.slope()? * 1000.0 / f64::from(rate.period_ms()))
+    }
+}
+
+impl<const N: usize> Default for Statistics<N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Rolling statistics over the last `N` sensor data readings.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct DataStatistics<const N: usize> {
+    object_voltage: Statistics<N>,
+    ambient_temperature: Statistics<N>,
+}
+
+impl<const N: usize> DataStatistics<N> {
+    /// Create new empty statistics.
+    pub const fn new() -> Self {
+        DataStatistics {
+            object_voltage: Statistics::new(),
+            ambient_temperature: Statistics::new(),
+        }
+    }
+
+    /// Add sensor data, replacing the oldest one if the window is full.
+    pub fn push(&mut self, data: SensorData) {
+        self.object_voltage.push(f64::from(data.object_voltage));
+        self.ambient_temperature
+            .push(data.ambient_temperature_celsius());
+    }
+
+    /// Remove all values.
+    pub fn clear(&mut self) {
+        self.object_voltage.clear();
+        self.ambient_temperature.clear();
+    }
+
+    /// Statistics of the raw object voltage.
+    pub fn object_voltage(&self) -> &Statistics<N> {
+        &self.object_voltage
+    }
+
+    /// Statistics of the ambient temperature in °C.
+    pub fn ambient_temperature(&self) -> &Statistics<N> {
+        &self.ambient_temperature
+    }
+
+    /// Ambient temperature drift rate in °C/s for data read at the given
+    /// conversion rate.
+    pub fn ambient_drift_rate(&self, rate: ConversionRate) -> Option<f64> {
+        self.ambient_temperature.slope_per_second(rate)
+    }
+}
+
+impl<const N: usize> Default for DataStatistics<N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn empty_statistics_have_no_values() {
+        let stats = Statistics::<4>::new();
+        assert!(stats.is_empty());
+        assert_eq!(None, stats.min());
+        assert_eq!(None, stats.max());
+        assert_eq!(None, stats.mean());
+        assert_eq!(None, stats.std_dev());
+        assert_eq!(None, stats.slope());
+    }
+
+    #[test]
+    fn can_calculate_statistics_over_window() {
+        let mut stats = Statistics::<4>::new();
+        for value in [100.0, 2.0, 4.0, f64::NAN, 4.0, 4.0, 5.0, 5.0, 7.0, 9.0] {
+            stats.push(value);
+        }
+        assert!(stats.is_full());
+        assert_eq!(Some(5.0), stats.min());
+        assert_eq!(Some(9.0), stats.max());
+        assert_eq!(Some(6.5), stats.mean());
+        assert_eq!(Some(libm::sqrt(2.75)), stats.std_dev());
+        assert_eq!(Some(1.4), stats.slope());
+        assert_eq!(Some(5.6), stats.slope_per_second(ConversionRate::Cps4));
+    }
+
+    #[test]
+    fn ignores_non_finite_values() {
+        let mut stats = Statistics::<4>::new();
+        for value in [1.0, f64::INFINITY, f64::NEG_INFINITY, f64::NAN, 3.0] {
+            stats.push(value);
+        }
+        assert_eq!(2, stats.len());
+        assert_eq!(Some(1.0), stats.min());
+        assert_eq!(Some(3.0), stats.max());
+        assert_eq!(Some(2.0), stats.mean());
+    }
+
+    #[test]
+    fn can_calculate_ambient_drift_rate() {
+        let mut stats = DataStatistics::<8>::new();
+        for i in 0..8 {
+            stats.push(SensorData {
+                object_voltage: 10,
+                ambient_temperature: 800 + i * 2,
+            });
+        }
+        assert_eq!(Some(0.0), stats.object_voltage().std_dev());
+        assert_eq!(Some(25.0), stats.ambient_temperature().min());
+        assert_eq!(Some(0.0625), stats.ambient_drift_rate(ConversionRate::Cps1));
+    }
+}
@@ -0,0 +1,384 @@
+//! Software threshold alerts
+
+use crate::{Error, Tmp006};
+use embedded_hal::{delay::DelayNs, i2c};
+
+/// Threshold limits.
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct Limits {
+    /// Low limit.
+    pub low: f64,
+    /// High limit.
+    pub high: f64,
+    /// Hysteresis for clearing a limit.
+    ///
+    /// A high limit is cleared once the value falls below `high - hysteresis`
+    /// and a low limit once the value rises above `low + hysteresis`.
+    /// Negative and `NaN` values are treated as `0.0`.
+    pub hysteresis: f64,
+    /// Number of consecutive samples needed to report an event.
+    ///
+    /// A value of `0` is treated as `1`.
+    pub debounce: u8,
+}
+
+/// Threshold state.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum ThresholdState {
+    /// Value within limits (default)
+    #[default]
+    Normal,
+    /// Value above the high limit
+    High,
+    /// Value below the low limit
+    Low,
+}
+
+/// Threshold event.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum ThresholdEvent {
+    /// The value rose above the high limit.
+    EnteredHigh,
+    /// The value fell below the high limit minus the hysteresis.
+    ClearedHigh,
+    /// The value fell below the low limit.
+    EnteredLow,
+    /// The value rose above the low limit plus the hysteresis.
+    ClearedLow,
+}
+
+/// Threshold evaluation with hysteresis and debouncing for a single value.
+///
+/// At most one event is reported per sample. For example, when the value
+/// jumps from above the high limit to below the low limit, `ClearedHigh` is
+/// reported first and `EnteredLow` is reported after the debounce count of
+/// further samples.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Threshold {
+    limits: Limits,
+    state: ThresholdState,
+    pending: Option<ThresholdEvent>,
+    count: u8,
+}
+
+impl Threshold {
+    /// Create new threshold evaluation.
+    ///
+    /// Inverted limits (`low > high`) are swapped and the hysteresis is
+    /// clamped to `0.0` or more, with `NaN` treated as `0.0`.
+    pub fn new(limits: Limits) -> Self {
+        let (low, high) = if limits.low > limits.high {
+            (limits.high, limits.low)
+        } else {
+            (limits.low, limits.high)
+        };
+        let hysteresis = if limits.hysteresis.is_nan() {
+            0.0
+        } else {
+            limits.hysteresis.max(0.0)
+        };
+        Threshold {
+            limits: Limits {
+                low,
+                high,
+                hysteresis,
+                ..limits
+            },
+            state: ThresholdState::Normal,
+            pending: None,
+            count: 0,
+        }
+    }
+
+    /// Threshold limits after swapping and clamping.
+    pub fn limits(&self) -> Limits {
+        self.limits
+    }
+
+    /// Current state.
+    pub fn state(&self) -> ThresholdState {
+        self.state
+    }
+
+    /// Evaluate a new value.
+    ///
+    /// `NaN` values are ignored.
+    pub fn update(&mut self, value: f64) -> Option<ThresholdEvent> {
+        if value.is_nan() {
+            return None;
+        }
+        let limits = &self.limits;
+        let candidate = match self.state {
+            ThresholdState::Normal if value > limits.high => Some(ThresholdEvent::EnteredHigh),
+            ThresholdState::Normal if value < limits.low => Some(ThresholdEvent::EnteredLow),
+            ThresholdState::High if value < limits.high - limits.hysteresis => {
+                Some(ThresholdEvent::ClearedHigh)
+            }
+            ThresholdState::Low if value > limits.low + limits.hysteresis => {
+                Some(ThresholdEvent::ClearedLow)
+            }
+            _ => None,
+        };
+        if candidate.is_none() || candidate != self.pending {
+            self.pending = candidate;
+            self.count = 0;
+        }
+        let event = self.pending?;
+        self.count = self.count.saturating_add(1);
+        if self.count < self.limits.debounce.max(1) {
+            return None;
+        }
+        self.state = match event {
+            ThresholdEvent::EnteredHigh => ThresholdState::High,
+            ThresholdEvent::EnteredLow => ThresholdState::Low,
+            ThresholdEvent::ClearedHigh | ThresholdEvent::ClearedLow => ThresholdState::Normal,
+        };
+        self.pending = None;
+        self.count = 0;
+        Some(event)
+    }
+
+    /// Return to the normal state.
+    pub fn reset(&mut self) {
+        self.state = ThresholdState::Normal;
+        self.pending = None;
+        self.count = 0;
+    }
+}
+
+/// Events reported by the threshold monitor.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct MonitorEvents {
+    /// Object temperature event.
+    pub object: Option<ThresholdEvent>,
+    /// Ambient temperature event.
+    pub ambient: Option<ThresholdEvent>,
+}
+
+/// Software threshold monitor for the object and ambient temperatures.
+///
+/// All limits are in °C, like the result of [`read_ambient_temperature()`].
+///
+/// [`read_ambient_temperature()`]: struct.Tmp006.html#method.read_ambient_temperature
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ThresholdMonitor {
+    object: Option<Threshold>,
+    ambient: Option<Threshold>,
+}
+
+impl ThresholdMonitor {
+    /// Create new threshold monitor.
+    ///
+    /// Values without limits are not monitored.
+    pub fn new(object: Option<Limits>, ambient: Option<Limits>) -> Self {
+        ThresholdMonitor {
+            object: object.map(Threshold::new),
+            ambient: ambient.map(Threshold::new),
+        }
+    }
+
+    /// Object temperature threshold state, if monitored.
+    pub fn object_state(&self) -> Option<ThresholdState> {
+        self.object.as_ref().map(Threshold::state)
+    }
+
+    /// Ambient temperature threshold state, if monitored.
+    pub fn ambient_state(&self) -> Option<ThresholdState> {
+        self.ambient.as_ref().map(Threshold::state)
+    }
+
+    /// Evaluate an object and an ambient temperature in °C.
+    pub fn update(&mut self, object_temperature: f64, ambient_temperature: f64) -> MonitorEvents {
+        MonitorEvents {
+            object: self
+                .object
+                .as_mut()
+                .and_then(|t| t.update(object_temperature)),
+            ambient: self
+                .ambient
+                .as_mut()
+                .and_then(|t| t.update(ambient_temperature)),
+        }
+    }
+
+    /// Read the sensor and evaluate the temperatures.
+    ///
+    /// The object temperature is calculated with the given calibration factor
+    /// and converted to °C. See: [`read_object_temperature()`].
+    ///
+    /// [`read_object_temperature()`]: struct.Tmp006.html#method.read_object_temperature
+    pub fn poll<I2C, D, E>(
+        &mut self,
+        sensor: &mut Tmp006<I2C, D>,
+        calibration_factor: f64,
+    ) -> nb::Result<MonitorEvents, Error<E>>
+    where
+        I2C: i2c::I2c<Error = E>,
+        D: DelayNs,
+    {
+        let data = sensor.read_sensor_data()?;
+        let object_temperature =
+            sensor.calculate_object_temperature(data, calibration_factor) - 273.15;
+        Ok(self.update(object_temperature, data.ambient_temperature_celsius()))
+    }
+
+    /// Return to the normal state.
+    pub fn reset(&mut self) {
+        self.object.iter_mut().for_each(Threshold::reset);
+        self.ambient.iter_mut().for_each(Threshold::reset);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const LIMITS: Limits = Limits {
+        low: 10.0,
+        high: 30.0,
+        hysteresis: 2.0,
+        debounce: 2,
+    };
+
+    fn run(threshold: &mut Threshold, values: &[f64]) -> [Option<ThresholdEvent>; 8] {
+        let mut events = [None; 8];
+        for (event, value) in events.iter_mut().zip(values) {
+            *event = threshold.update(*value);
+        }
+        events
+    }
+
+    #[test]
+    fn reports_high_after_debounce() {
+        use ThresholdEvent::*;
+        let mut threshold = Threshold::new(LIMITS);
+        let events = run(&mut threshold, &[31.0, 29.0, 31.0, 31.0, 29.0, 27.0, 27.0]);
+        let expected = [
+            None,
+            None,
+            None,
+            Some(EnteredHigh),
+            None,
+            None,
+            Some(ClearedHigh),
+            None,
+        ];
+        assert_eq!(expected, events);
+        assert_eq!(ThresholdState::Normal, threshold.state());
+    }
+
+    #[test]
+    fn hysteresis_edge_does_not_clear() {
+        let mut threshold = Threshold::new(Limits {
+            debounce: 1,
+            ..LIMITS
+        });
+        assert_eq!(Some(ThresholdEvent::EnteredHigh), threshold.update(30.5));
+        assert_eq!(None, threshold.update(28.0));
+        assert_eq!(None, threshold.update(30.0));
+        assert_eq!(None, threshold.update(f64::NAN));
+        assert_eq!(ThresholdState::High, threshold.state());
+        assert_eq!(Some(ThresholdEvent::ClearedHigh), threshold.update(27.9));
+    }
+
+    #[test]
+    fn clamps_hysteresis() {
+        let new = |hysteresis| {
+            Threshold::new(Limits {
+                hysteresis,
+                ..LIMITS
+            })
+        };
+        assert_eq!(2.0, new(2.0).limits().hysteresis);
+        assert_eq!(0.0, new(-1.0).limits().hysteresis);
+        assert_eq!(0.0, new(f64::NAN).limits().hysteresis);
+    }
+
+    #[test]
+    fn negative_hysteresis_does_not_clear_above_limit() {
+        let mut threshold = Threshold::new(Limits {
+            hysteresis: -5.0,
+            debounce: 1,
+            ..LIMITS
+        });
+        assert_eq!(Some(ThresholdEvent::EnteredHigh), threshold.update(31.0));
+        assert_eq!(None, threshold.update(32.0));
+        assert_eq!(ThresholdState::High, threshold.state());
+    }
+
+    #[test]
+    fn swaps_inverted_limits() {
+        let mut threshold = Threshold::new(Limits {
+            low: 30.0,
+            high: 10.0,
+            debounce: 1,
+            ..LIMITS
+        });
+        assert_eq!(
+            (10.0, 30.0),
+            (threshold.limits().low, threshold.limits().high)
+        );
+        assert_eq!(None, threshold.update(20.0));
+        assert_eq!(Some(ThresholdEvent::EnteredHigh), threshold.update(31.0));
+    }
+
+    #[test]
+    fn limit_edge_does_not_enter() {
+        let mut threshold = Threshold::new(Limits {
+            debounce: 1,
+            ..LIMITS
+        });
+        assert_eq!(None, threshold.update(30.0));
+        assert_eq!(None, threshold.update(10.0));
+        assert_eq!(ThresholdState::Normal, threshold.state());
+    }
+
+    #[test]
+    fn reports_low_after_clearing_high() {
+        use ThresholdEvent::*;
+        let mut threshold = Threshold::new(LIMITS);
+        let events = run(
+            &mut threshold,
+            &[40.0, 40.0, 0.0, 0.0, 0.0, 0.0, 12.5, 12.5],
+        );
+        let expected = [
+            None,
+            Some(EnteredHigh),
+            None,
+            Some(ClearedHigh),
+            None,
+            Some(EnteredLow),
+            None,
+            Some(ClearedLow),
+        ];
+        assert_eq!(expected, events);
+    }
+
+    #[test]
+    fn monitors_configured_values_only() {
+        let mut monitor = ThresholdMonitor::new(
+            None,
+            Some(Limits {
+                debounce: 1,
+                ..LIMITS
+            }),
+        );
+        let events = monitor.update(100.0, 35.0);
+        assert_eq!(None, events.object);
+        assert_eq!(Some(ThresholdEvent::EnteredHigh), events.ambient);
+        assert_eq!(None, monitor.object_state());
+        assert_eq!(Some(ThresholdState::High), monitor.ambient_state());
+        monitor.reset();
+        assert_eq!(Some(ThresholdState::Normal), monitor.ambient_state());
+    }
+}
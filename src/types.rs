@@ -1,14 +1,102 @@
 //! Type definition
 
+use crate::{NoDelay, RetryPolicy};
+use core::fmt;
+
 /// All possible errors in this crate
 #[derive(Debug)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
 pub enum Error<E> {
     /// I²C bus error
     I2C(E),
+    /// Timed out waiting for the device
+    Timeout,
+    /// The configuration read back after writing it does not match.
+    ///
+    /// Contains the mode, conversion rate and DRDY pin bits of the
+    /// configuration register most significant byte.
+    VerificationFailed {
+        /// Written configuration bits
+        expected: u8,
+        /// Configuration bits read back
+        actual: u8,
+    },
+    /// The device does not identify itself as a TMP006.
+    InvalidDeviceId {
+        /// Manufacturer ID read
+        manufacturer_id: u16,
+        /// Device ID read
+        device_id: u16,
+    },
+}
+
+/// Error kind
+///
+/// This is the error category without the details of each variant.
+/// See: [`Error::kind()`](enum.Error.html#method.kind).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum ErrorKind {
+    /// I²C bus error
+    I2C,
+    /// Timed out waiting for the device
+    Timeout,
+    /// Configuration read back does not match
+    VerificationFailed,
+    /// The device does not identify itself as a TMP006
+    InvalidDeviceId,
+}
+
+impl<E> Error<E> {
+    /// Error kind
+    pub fn kind(&self) -> ErrorKind {
+        match self {
+            Error::I2C(_) => ErrorKind::I2C,
+            Error::Timeout => ErrorKind::Timeout,
+            Error::VerificationFailed { .. } => ErrorKind::VerificationFailed,
+            Error::InvalidDeviceId { .. } => ErrorKind::InvalidDeviceId,
+        }
+    }
 }
 
+impl<E: fmt::Debug> fmt::Display for Error<E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::I2C(e) => write!(f, "I²C bus error: {:?}", e),
+            Error::Timeout => write!(f, "timed out waiting for the device"),
+            Error::VerificationFailed { expected, actual } => write!(
+                f,
+                "configuration verification failed: wrote 0x{:02X}, read back 0x{:02X}",
+                expected, actual
+            ),
+            Error::InvalidDeviceId {
+                manufacturer_id,
+                device_id,
+            } => write!(
+                f,
+                "invalid device ID: manufacturer ID 0x{:04X}, device ID 0x{:04X}",
+                manufacturer_id, device_id
+            ),
+        }
+    }
+}
+
+impl<E: fmt::Debug> core::error::Error for Error<E> {}
+
+/// Manufacturer ID of the TMP006.
+pub(crate) const MANUFACTURER_ID: u16 = 0x5449;
+/// Device ID of the TMP006.
+pub(crate) const DEVICE_ID: u16 = 0x0067;
+
+/// Typical supply current while converting in µA.
+pub(crate) const ACTIVE_SUPPLY_CURRENT_UA: f64 = 240.0;
+/// Typical supply current in power-down in µA.
+pub(crate) const POWER_DOWN_SUPPLY_CURRENT_UA: f64 = 0.5;
+
 /// ADC conversion rate
 #[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
 pub enum ConversionRate {
     /// 4 conversions per second
     Cps4,
@@ -23,6 +111,122 @@ pub enum ConversionRate {
     Cps0_25,
 }
 
+impl ConversionRate {
+    /// Conversion period in milliseconds.
+    ///
+    /// This is also the total conversion time.
+    pub fn period_ms(self) -> u32 {
+        match self {
+            ConversionRate::Cps4 => 250,
+            ConversionRate::Cps2 => 500,
+            ConversionRate::Cps1 => 1000,
+            ConversionRate::Cps0_5 => 2000,
+            ConversionRate::Cps0_25 => 4000,
+        }
+    }
+
+    /// Number of averaged ADC samples per conversion.
+    pub fn averaged_samples(self) -> u8 {
+        match self {
+            ConversionRate::Cps4 => 1,
+            ConversionRate::Cps2 => 2,
+            ConversionRate::Cps1 => 4,
+            ConversionRate::Cps0_5 => 8,
+            ConversionRate::Cps0_25 => 16,
+        }
+    }
+
+    /// Typical RMS noise of the object voltage in µV.
+    pub fn typical_noise_uv(self) -> f64 {
+        match self {
+            ConversionRate::Cps4 => 0.5,
+            ConversionRate::Cps2 => 0.35,
+            ConversionRate::Cps1 => 0.25,
+            ConversionRate::Cps0_5 => 0.18,
+            ConversionRate::Cps0_25 => 0.125,
+        }
+    }
+
+    /// Typical supply current in µA while converting.
+    ///
+    /// The device converts continuously, so this is the same for all rates.
+    pub fn supply_current_ua(self) -> f64 {
+        ACTIVE_SUPPLY_CURRENT_UA
+    }
+
+    /// Conversion rate with the given period in milliseconds, if any.
+    pub fn from_period_ms(period_ms: u32) -> Option<Self> {
+        ALL_CONVERSION_RATES
+            .iter()
+            .copied()
+            .find(|rate| rate.period_ms() == period_ms)
+    }
+
+    /// Fastest conversion rate whose period is at least the given period
+    /// in milliseconds.
+    ///
+    /// Returns `None` if the period is longer than the slowest rate.
+    pub fn fastest_at_least(period_ms: u32) -> Option<Self> {
+        ALL_CONVERSION_RATES
+            .iter()
+            .copied()
+            .find(|rate| rate.period_ms() >= period_ms)
+    }
+}
+
+/// All conversion rates from the fastest to the slowest one.
+pub(crate) const ALL_CONVERSION_RATES: [ConversionRate; 5] = [
+    ConversionRate::Cps4,
+    ConversionRate::Cps2,
+    ConversionRate::Cps1,
+    ConversionRate::Cps0_5,
+    ConversionRate::Cps0_25,
+];
+
+/// Invalid CR2..CR0 bit encoding of the conversion rate.
+///
+/// Contains the rejected value.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct InvalidConversionRate(pub u8);
+
+impl fmt::Display for InvalidConversionRate {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "invalid conversion rate encoding: {}", self.0)
+    }
+}
+
+impl core::error::Error for InvalidConversionRate {}
+
+/// Conversion rate from the CR2..CR0 bit encoding: `[0..4]`
+impl TryFrom<u8> for ConversionRate {
+    type Error = InvalidConversionRate;
+
+    fn try_from(bits: u8) -> Result<Self, Self::Error> {
+        match bits {
+            0 => Ok(ConversionRate::Cps4),
+            1 => Ok(ConversionRate::Cps2),
+            2 => Ok(ConversionRate::Cps1),
+            3 => Ok(ConversionRate::Cps0_5),
+            4 => Ok(ConversionRate::Cps0_25),
+            _ => Err(InvalidConversionRate(bits)),
+        }
+    }
+}
+
+/// CR2..CR0 bit encoding of the conversion rate.
+impl From<ConversionRate> for u8 {
+    fn from(rate: ConversionRate) -> Self {
+        match rate {
+            ConversionRate::Cps4 => 0,
+            ConversionRate::Cps2 => 1,
+            ConversionRate::Cps1 => 2,
+            ConversionRate::Cps0_5 => 3,
+            ConversionRate::Cps0_25 => 4,
+        }
+    }
+}
+
 /// Data as read from the sensor.
 ///
 /// These values can be used to calculate the object temperature as done in
@@ -30,15 +234,51 @@ pub enum ConversionRate {
 ///
 /// [`read_object_temperature()`]: struct.Tmp006.html#method.read_object_temperature
 #[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
 pub struct SensorData {
     /// Object voltage: `[-32768..32767]`
     pub object_voltage: i16,
-    /// Ambient temperature: `[-8192..8191]`
+    /// Ambient temperature: `[-8192..8191]` in 1/32 °C units
     pub ambient_temperature: i16,
 }
 
+impl SensorData {
+    /// Decode the sensor data from the raw `V_OBJECT` and `T_AMBIENT`
+    /// register contents.
+    ///
+    /// The object voltage is a 16-bit two's complement value. The ambient
+    /// temperature is a 14-bit left-justified two's complement value where
+    /// the two least significant bits are ignored.
+    pub fn from_registers(v_obj: u16, t_amb: u16) -> Self {
+        SensorData {
+            object_voltage: v_obj as i16,
+            ambient_temperature: (t_amb as i16) >> 2,
+        }
+    }
+
+    /// Ambient temperature in °C.
+    pub fn ambient_temperature_celsius(&self) -> f64 {
+        f64::from(self.ambient_temperature) / 32.0
+    }
+
+    /// Encode the sensor data into `V_OBJECT` and `T_AMBIENT` register
+    /// contents.
+    ///
+    /// This is the inverse of [`from_registers()`](#method.from_registers)
+    /// for ambient temperatures in the `[-8192..8191]` range.
+    pub fn to_registers(&self) -> (u16, u16) {
+        (
+            self.object_voltage as u16,
+            (self.ambient_temperature << 2) as u16,
+        )
+    }
+}
+
 /// Possible slave addresses
 #[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
 pub enum SlaveAddr {
     /// Default slave address
     #[default]
@@ -63,27 +303,55 @@ impl SlaveAddr {
 
 pub const DEVICE_BASE_ADDRESS: u8 = 0b100_0000;
 
-pub struct Register;
+/// Device register
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Register {
+    /// Sensor voltage result (`V_OBJECT`)
+    ObjectVoltage,
+    /// Ambient temperature result (`T_AMBIENT`)
+    AmbientTemperature,
+    /// Configuration
+    Config,
+    /// Manufacturer ID
+    ManufacturerId,
+    /// Device ID
+    DeviceId,
+}
+
 impl Register {
-    pub const V_OBJECT: u8 = 0x00;
-    pub const TEMP_AMBIENT: u8 = 0x01;
-    pub const CONFIG: u8 = 0x02;
-    pub const MANUFAC_ID: u8 = 0xFE;
-    pub const DEVICE_ID: u8 = 0xFE;
+    /// Register address.
+    pub fn address(self) -> u8 {
+        match self {
+            Register::ObjectVoltage => 0x00,
+            Register::AmbientTemperature => 0x01,
+            Register::Config => 0x02,
+            Register::ManufacturerId => 0xFE,
+            Register::DeviceId => 0xFF,
+        }
+    }
 }
 
+/// Configuration register most significant byte bit flags
 pub struct BitFlagsHigh;
 impl BitFlagsHigh {
+    /// Software reset
     pub const SW_RESET: u8 = 0b1000_0000;
+    /// Mode of operation: continuous conversion or power-down
     pub const MOD: u8 = 0b0111_0000;
+    /// Conversion rate bit 2
     pub const CR2: u8 = 0b0000_1000;
+    /// Conversion rate bit 1
     pub const CR1: u8 = 0b0000_0100;
+    /// Conversion rate bit 0
     pub const CR0: u8 = 0b0000_0010;
+    /// DRDY pin enable
     pub const DRDY_EN: u8 = 0b0000_0001;
 }
 
+/// Configuration register least significant byte bit flags
 pub struct BitFlagsLow;
 impl BitFlagsLow {
+    /// Data ready
     pub const DRDY: u8 = 0b1000_0000;
 }
 
@@ -94,13 +362,34 @@ pub struct ConfigHigh {
 
 /// TMP006 device driver.
 #[derive(Debug)]
-pub struct Tmp006<I2C> {
+pub struct Tmp006<I2C, D = NoDelay> {
     /// The concrete I²C device implementation.
     pub(crate) i2c: I2C,
+    /// Delay used between retries.
+    pub(crate) delay: D,
+    /// I²C retry policy.
+    pub(crate) retry_policy: RetryPolicy,
     /// The I²C device address.
     pub(crate) address: u8,
     /// Configuration register status.
     pub(crate) config: ConfigHigh,
+    /// Number of reads between configuration checks. `0` means disabled.
+    pub(crate) config_check_interval: u16,
+    /// Number of reads since the last configuration check.
+    pub(crate) reads_since_config_check: u16,
+    /// Number of times the configuration was written again.
+    pub(crate) reconfiguration_count: u32,
+    /// Whether the configuration is read back after writing it.
+    pub(crate) verify_writes: bool,
+    /// Number of failed I²C transfers.
+    pub(crate) error_count: u32,
+    /// Number of retried I²C transfers.
+    pub(crate) retry_count: u32,
+    /// Time in milliseconds at which `read_fresh_sensor_data()` expects the
+    /// next conversion.
+    pub(crate) next_fresh_ms: Option<u64>,
+    /// Number of readings returned by `read_fresh_sensor_data()`.
+    pub(crate) fresh_read_count: u32,
 }
 
 #[cfg(test)]
@@ -136,4 +425,103 @@ mod tests {
             SlaveAddr::Alternative(true, true, true).addr(DEVICE_BASE_ADDRESS)
         );
     }
+
+    #[test]
+    fn can_convert_conversion_rate_bits() {
+        for rate in ALL_CONVERSION_RATES {
+            assert_eq!(Ok(rate), ConversionRate::try_from(u8::from(rate)));
+        }
+        for bits in 5..=u8::MAX {
+            assert_eq!(
+                Err(InvalidConversionRate(bits)),
+                ConversionRate::try_from(bits)
+            );
+        }
+    }
+
+    #[test]
+    fn conversion_rate_metadata_is_consistent() {
+        for rate in ALL_CONVERSION_RATES {
+            assert_eq!(250 * u32::from(rate.averaged_samples()), rate.period_ms());
+            assert_eq!(Some(rate), ConversionRate::from_period_ms(rate.period_ms()));
+        }
+        assert_eq!(None, ConversionRate::from_period_ms(300));
+    }
+
+    #[test]
+    fn can_find_fastest_conversion_rate_at_least() {
+        assert_eq!(
+            Some(ConversionRate::Cps4),
+            ConversionRate::fastest_at_least(0)
+        );
+        assert_eq!(
+            Some(ConversionRate::Cps2),
+            ConversionRate::fastest_at_least(251)
+        );
+        assert_eq!(
+            Some(ConversionRate::Cps1),
+            ConversionRate::fastest_at_least(1000)
+        );
+        assert_eq!(
+            Some(ConversionRate::Cps0_25),
+            ConversionRate::fastest_at_least(4000)
+        );
+        assert_eq!(None, ConversionRate::fastest_at_least(4001));
+    }
+
+    #[test]
+    fn can_decode_ambient_temperature_datasheet_values() {
+        // Temperature data format table of the datasheet:
+        // (temperature in °C, register value)
+        let table = [
+            (150.0, 0x4B00),
+            (125.0, 0x3E80),
+            (100.0, 0x3200),
+            (80.0, 0x2800),
+            (75.0, 0x2580),
+            (50.0, 0x1900),
+            (25.0, 0x0C80),
+            (0.03125, 0x0004),
+            (0.0, 0x0000),
+            (-0.03125, 0xFFFC),
+            (-25.0, 0xF380),
+            (-55.0, 0xE480),
+        ];
+        for (celsius, register) in table {
+            let data = SensorData::from_registers(0, register);
+            assert_eq!(celsius, data.ambient_temperature_celsius());
+        }
+    }
+
+    #[test]
+    fn can_decode_all_ambient_temperature_registers() {
+        for register in 0..=u16::MAX {
+            let data = SensorData::from_registers(0, register);
+            let expected = i32::from(register as i16).div_euclid(4);
+            assert_eq!(expected, i32::from(data.ambient_temperature));
+            assert!((-8192..=8191).contains(&data.ambient_temperature));
+            assert_eq!((0, register & !0b11), data.to_registers());
+        }
+    }
+
+    #[test]
+    fn can_decode_all_object_voltage_registers() {
+        for register in 0..=u16::MAX {
+            let data = SensorData::from_registers(register, 0);
+            assert_eq!(i32::from(register as i16), i32::from(data.object_voltage));
+            assert_eq!((register, 0), data.to_registers());
+        }
+    }
+
+    #[test]
+    fn can_encode_all_ambient_temperatures() {
+        for ambient_temperature in -8192..=8191 {
+            let data = SensorData {
+                object_voltage: 0,
+                ambient_temperature,
+            };
+            let (v_obj, t_amb) = data.to_registers();
+            assert_eq!(data, SensorData::from_registers(v_obj, t_amb));
+        }
+    }
 }
@@ -0,0 +1,198 @@
+//! Fixed-capacity sample window
+
+/// Ring buffer holding the last `capacity` values, at most `N`.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct Window<const N: usize> {
+    values: [f64; N],
+    capacity: usize,
+    next: usize,
+    len: usize,
+}
+
+impl<const N: usize> Window<N> {
+    pub(crate) const fn new() -> Self {
+        Self::with_capacity(N)
+    }
+
+    /// Create a window holding fewer values than `N`.
+    pub(crate) const fn with_capacity(capacity: usize) -> Self {
+        Window {
+            values: [0.0; N],
+            capacity: if capacity < N { capacity } else { N },
+            next: 0,
+            len: 0,
+        }
+    }
+
+    /// Add a value, replacing the oldest one if the window is full.
+    pub(crate) fn push(&mut self, value: f64) {
+        if self.capacity == 0 {
+            return;
+        }
+        self.values[self.next] = value;
+        self.next = (self.next + 1) % self.capacity;
+        self.len = (self.len + 1).min(self.capacity);
+    }
+
+    pub(crate) fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    pub(crate) fn len(&self) -> usize {
+        self.len
+    }
+
+    pub(crate) fn is_full(&self) -> bool {
+        self.len == self.capacity
+    }
+
+    pub(crate) fn clear(&mut self) {
+        self.next = 0;
+        self.len = 0;
+    }
+
+    /// Values from the oldest to the newest one.
+    pub(crate) fn iter(&self) -> impl Iterator<Item = f64> + '_ {
+        let capacity = self.capacity.max(1);
+        let start = (self.next + capacity - self.len) % capacity;
+        (0..self.len).map(move |i| self.values[(start + i) % capacity])
+    }
+}
+
+/// Windows are equal if they have the same capacity and hold the same values
+/// in the same order.
+impl<const N: usize> PartialEq for Window<N> {
+    fn eq(&self, other: &Self) -> bool {
+        self.capacity == other.capacity && self.iter().eq(other.iter())
+    }
+}
+
+/// Serialized as the capacity followed by the values from the oldest to the
+/// newest one, so that the window does not depend on its position in the
+/// ring buffer.
+#[cfg(feature = "serde")]
+impl<const N: usize> serde::Serialize for Window<N> {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        use serde::ser::SerializeTuple;
+
+        struct Values<'a, const N: usize>(&'a Window<N>);
+
+        impl<const N: usize> serde::Serialize for Values<'_, N> {
+            fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                serializer.collect_seq(self.0.iter())
+            }
+        }
+
+        let mut tuple = serializer.serialize_tuple(2)?;
+        tuple.serialize_element(&self.capacity)?;
+        tuple.serialize_element(&Values(self))?;
+        tuple.end()
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de, const N: usize> serde::Deserialize<'de> for Window<N> {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        use core::fmt;
+        use serde::de::{DeserializeSeed, Error, SeqAccess, Visitor};
+
+        struct Values<const N: usize>(Window<N>);
+
+        impl<'de, const N: usize> DeserializeSeed<'de> for Values<N> {
+            type Value = Window<N>;
+
+            fn deserialize<D: serde::Deserializer<'de>>(
+                self,
+                deserializer: D,
+            ) -> Result<Self::Value, D::Error> {
+                deserializer.deserialize_seq(self)
+            }
+        }
+
+        impl<'de, const N: usize> Visitor<'de> for Values<N> {
+            type Value = Window<N>;
+
+            fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                write!(f, "at most {} values", self.0.capacity)
+            }
+
+            fn visit_seq<A: SeqAccess<'de>>(mut self, mut seq: A) -> Result<Self::Value, A::Error> {
+                while let Some(value) = seq.next_element::<f64>()? {
+                    if self.0.is_full() {
+                        return Err(A::Error::invalid_length(self.0.len() + 1, &self));
+                    }
+                    self.0.push(value);
+                }
+                Ok(self.0)
+            }
+        }
+
+        struct WindowVisitor<const N: usize>;
+
+        impl<'de, const N: usize> Visitor<'de> for WindowVisitor<N> {
+            type Value = Window<N>;
+
+            fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                f.write_str("the window capacity followed by its values")
+            }
+
+            fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Self::Value, A::Error> {
+                let capacity: usize = seq
+                    .next_element()?
+                    .ok_or_else(|| A::Error::invalid_length(0, &self))?;
+                if capacity > N {
+                    return Err(A::Error::custom("window capacity too large"));
+                }
+                seq.next_element_seed(Values(Window::with_capacity(capacity)))?
+                    .ok_or_else(|| A::Error::invalid_length(1, &self))
+            }
+        }
+
+        deserializer.deserialize_tuple(2, WindowVisitor)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn iterates_from_oldest_to_newest() {
+        let mut window = Window::<3>::new();
+        window.push(1.0);
+        window.push(2.0);
+        assert!(window.iter().eq([1.0, 2.0]));
+        window.push(3.0);
+        window.push(4.0);
+        assert!(window.is_full());
+        assert!(window.iter().eq([2.0, 3.0, 4.0]));
+        window.clear();
+        assert_eq!(0, window.len());
+        assert_eq!(0, window.iter().count());
+    }
+
+    #[test]
+    fn can_limit_capacity() {
+        let mut window = Window::<4>::with_capacity(2);
+        for value in [1.0, 2.0, 3.0] {
+            window.push(value);
+        }
+        assert!(window.is_full());
+        assert!(window.iter().eq([2.0, 3.0]));
+        assert_eq!(4, Window::<4>::with_capacity(10).capacity());
+    }
+
+    #[test]
+    fn compares_values_in_order() {
+        let mut rotated = Window::<2>::new();
+        for value in [1.0, 2.0, 3.0] {
+            rotated.push(value);
+        }
+        let mut window = Window::<2>::new();
+        window.push(2.0);
+        window.push(3.0);
+        assert_eq!(window, rotated);
+        window.push(4.0);
+        assert_ne!(window, rotated);
+    }
+}
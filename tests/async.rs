@@ -0,0 +1,168 @@
+#![cfg(feature = "async")]
+
+use core::convert::Infallible;
+use embedded_hal::digital::ErrorKind;
+use embedded_hal_async::digital::Wait;
+use embedded_hal_mock::eh1::{
+    delay::NoopDelay,
+    i2c::{Mock as I2cMock, Transaction as I2cTrans},
+};
+use futures::{executor::block_on, StreamExt};
+use tmp006::{ConversionRate, Error, SensorData, SlaveAddr, StreamError, Tmp006Async};
+
+const DEV_ADDR: u8 = 0b100_0000;
+const CONFIG: u8 = 0x02;
+const V_OBJECT: u8 = 0x00;
+const TEMP_AMBIENT: u8 = 0x01;
+const DRDY: u8 = 0b1000_0000;
+
+fn new(transactions: &[I2cTrans]) -> Tmp006Async<I2cMock> {
+    Tmp006Async::new(I2cMock::new(transactions), SlaveAddr::default())
+}
+
+fn destroy(tmp: Tmp006Async<I2cMock>) {
+    tmp.destroy().done();
+}
+
+fn data_transactions(drdy: u8, v_lsb: u8) -> Vec<I2cTrans> {
+    let mut trans = vec![I2cTrans::write_read(DEV_ADDR, vec![CONFIG], vec![0, drdy])];
+    if drdy != 0 {
+        trans.push(I2cTrans::write_read(
+            DEV_ADDR,
+            vec![V_OBJECT],
+            vec![0, v_lsb],
+        ));
+        trans.push(I2cTrans::write_read(
+            DEV_ADDR,
+            vec![TEMP_AMBIENT],
+            vec![0, 4],
+        ));
+    }
+    trans
+}
+
+fn expected(object_voltage: i16) -> SensorData {
+    SensorData {
+        object_voltage,
+        ambient_temperature: 1,
+    }
+}
+
+#[test]
+fn can_set_conversion_rate() {
+    let trans = [I2cTrans::write(DEV_ADDR, vec![CONFIG, 0b0111_0000, 0])];
+    let mut tmp = new(&trans);
+    block_on(tmp.set_conversion_rate(ConversionRate::Cps4)).unwrap();
+    destroy(tmp);
+}
+
+#[test]
+fn read_sensor_data_waits_for_data_ready() {
+    let mut trans = data_transactions(0, 0);
+    trans.extend(data_transactions(DRDY, 1));
+    let mut tmp = new(&trans);
+    let data = block_on(tmp.read_sensor_data(&mut NoopDelay)).unwrap();
+    assert_eq!(expected(1), data);
+    destroy(tmp);
+}
+
+#[test]
+fn read_sensor_data_times_out() {
+    let mut trans = vec![I2cTrans::write(DEV_ADDR, vec![CONFIG, 0b0111_0000, 0])];
+    for _ in 0..=500 / 10 {
+        trans.extend(data_transactions(0, 0));
+    }
+    let mut tmp = new(&trans);
+    block_on(tmp.set_conversion_rate(ConversionRate::Cps4)).unwrap();
+    match block_on(tmp.read_sensor_data(&mut NoopDelay)) {
+        Err(Error::Timeout) => (),
+        _ => panic!("Did not time out."),
+    }
+    destroy(tmp);
+}
+
+#[test]
+fn can_stream_paced_by_conversion_rate() {
+    let mut trans = data_transactions(DRDY, 1);
+    trans.extend(data_transactions(0, 0));
+    trans.extend(data_transactions(DRDY, 2));
+    let mut tmp = new(&trans);
+    {
+        let mut stream = core::pin::pin!(tmp.stream(NoopDelay));
+        assert_eq!(expected(1), block_on(stream.next()).unwrap().unwrap());
+        assert_eq!(expected(2), block_on(stream.next()).unwrap().unwrap());
+    }
+    destroy(tmp);
+}
+
+struct DrdyPin;
+
+impl embedded_hal::digital::ErrorType for DrdyPin {
+    type Error = Infallible;
+}
+
+impl Wait for DrdyPin {
+    async fn wait_for_high(&mut self) -> Result<(), Self::Error> {
+        Ok(())
+    }
+    async fn wait_for_low(&mut self) -> Result<(), Self::Error> {
+        Ok(())
+    }
+    async fn wait_for_rising_edge(&mut self) -> Result<(), Self::Error> {
+        Ok(())
+    }
+    async fn wait_for_falling_edge(&mut self) -> Result<(), Self::Error> {
+        Ok(())
+    }
+    async fn wait_for_any_edge(&mut self) -> Result<(), Self::Error> {
+        Ok(())
+    }
+}
+
+#[test]
+fn can_stream_paced_by_drdy_pin() {
+    let mut trans = data_transactions(DRDY, 1);
+    trans.extend(data_transactions(DRDY, 2));
+    let mut tmp = new(&trans);
+    let readings: Vec<_> = block_on(tmp.stream_with_drdy_pin(DrdyPin).take(2).collect());
+    assert_eq!(expected(1), *readings[0].as_ref().unwrap());
+    assert_eq!(expected(2), *readings[1].as_ref().unwrap());
+    destroy(tmp);
+}
+
+struct FailingPin;
+
+impl embedded_hal::digital::ErrorType for FailingPin {
+    type Error = ErrorKind;
+}
+
+impl Wait for FailingPin {
+    async fn wait_for_high(&mut self) -> Result<(), Self::Error> {
+        Err(ErrorKind::Other)
+    }
+    async fn wait_for_low(&mut self) -> Result<(), Self::Error> {
+        Err(ErrorKind::Other)
+    }
+    async fn wait_for_rising_edge(&mut self) -> Result<(), Self::Error> {
+        Err(ErrorKind::Other)
+    }
+    async fn wait_for_falling_edge(&mut self) -> Result<(), Self::Error> {
+        Err(ErrorKind::Other)
+    }
+    async fn wait_for_any_edge(&mut self) -> Result<(), Self::Error> {
+        Err(ErrorKind::Other)
+    }
+}
+
+#[test]
+fn stream_returns_drdy_pin_errors() {
+    let mut tmp = new(&[]);
+    {
+        let mut stream = core::pin::pin!(tmp.stream_with_drdy_pin(FailingPin));
+        match block_on(stream.next()) {
+            Some(Err(StreamError::Pin(ErrorKind::Other))) => (),
+            _ => panic!("Did not return the pin error."),
+        }
+    }
+    destroy(tmp);
+}
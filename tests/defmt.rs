@@ -0,0 +1,29 @@
+#![cfg(feature = "defmt")]
+
+use tmp006::{
+    ConversionRate, Error, Fault, Health, HealthCounters, Limits, MonitorEvents, PresenceEvent,
+    RegisterSnapshot, RetryPolicy, Sample, SensorData, SettlingState, SlaveAddr, ThresholdEvent,
+    ThresholdState,
+};
+
+fn assert_format<T: defmt::Format>() {}
+
+#[test]
+fn types_implement_format() {
+    assert_format::<Error<defmt::Str>>();
+    assert_format::<SensorData>();
+    assert_format::<ConversionRate>();
+    assert_format::<SlaveAddr>();
+    assert_format::<RetryPolicy>();
+    assert_format::<Limits>();
+    assert_format::<Sample>();
+    assert_format::<RegisterSnapshot>();
+    assert_format::<HealthCounters>();
+    assert_format::<Health>();
+    assert_format::<Fault>();
+    assert_format::<ThresholdState>();
+    assert_format::<ThresholdEvent>();
+    assert_format::<MonitorEvents>();
+    assert_format::<PresenceEvent>();
+    assert_format::<SettlingState>();
+}
@@ -1,5 +1,13 @@
-use embedded_hal_mock::eh1::i2c::{Mock as I2cMock, Transaction as I2cTrans};
-use tmp006::{ConversionRate, SensorData, SlaveAddr, Tmp006};
+use embedded_hal::i2c::{ErrorKind, NoAcknowledgeSource};
+use embedded_hal_mock::eh1::{
+    delay::NoopDelay,
+    i2c::{Mock as I2cMock, Transaction as I2cTrans},
+};
+use tmp006::{
+    ConversionRate, Error, Fault, Health, HealthMonitor, Limits, RetryPolicy, Sampler, Scheduler,
+    SensorData, SettlingDetector, SettlingState, SlaveAddr, ThresholdEvent, ThresholdMonitor,
+    Tmp006,
+};
 
 const DEV_ADDR: u8 = 0b100_0000;
 
@@ -9,7 +17,7 @@ impl Register {
     const TEMP_AMBIENT: u8 = 0x01;
     const CONFIG: u8 = 0x02;
     const MANUFAC_ID: u8 = 0xFE;
-    const DEVICE_ID: u8 = 0xFE;
+    const DEVICE_ID: u8 = 0xFF;
 }
 
 struct BitFlagsHigh;
@@ -156,6 +164,9 @@ sensor_data_test!(can_read_voltage_min, -32768, 0, 0x80, 0x00, 0, 0);
 sensor_data_test!(can_read_ambient_t_max, 0, 8191, 0, 0, 0x7F, 0xFC);
 sensor_data_test!(can_read_ambient_t_0, 0, 0, 0, 0, 0, 0);
 sensor_data_test!(can_read_ambient_t_min, 0, -8192, 0, 0, 0x80, 0x00);
+sensor_data_test!(can_read_ambient_t_minus_1, 0, -1, 0, 0, 0xFF, 0xFC);
+sensor_data_test!(can_read_ambient_t_minus_1_low_bits, 0, -1, 0, 0, 0xFF, 0xFF);
+sensor_data_test!(can_read_ambient_t_minus_25, 0, -800, 0, 0, 0xF3, 0x80);
 
 write_read_test!(
     can_read_data_ready,
@@ -222,19 +233,19 @@ fn cannot_read_data_if_not_ready() {
 fn can_read_object_temperature_real_data() {
     /* For some example values of V_obj=-100 and T_ambient=675.
         If you put this into maxima (the program) (or mathematica) you should
-        be able to get the same result: 278.5701125352883.
+        be able to get the same result: 296.1167531649231.
         sqrt(sqrt(
-            (675/128 + 273.15)^4+(
+            (675/32 + 273.15)^4+(
                 ((-100*156.25*10^-9)
-                    - (-2.94e-5 -5.7e-7*((675/128 + 273.15)-298.15)
-                    + 4.63e-9*((675/128 + 273.15)-298.15)²))
+                    - (-2.94e-5 -5.7e-7*((675/32 + 273.15)-298.15)
+                    + 4.63e-9*((675/32 + 273.15)-298.15)²))
                 + 13.4 * ((-100*156.25*10^-9)
-                - (-2.94e-5 -5.7e-7*((675/128 + 273.15)-298.15)
-                    + 4.63e-9*((675/128 + 273.15)-298.15)²))²)
+                - (-2.94e-5 -5.7e-7*((675/32 + 273.15)-298.15)
+                    + 4.63e-9*((675/32 + 273.15)-298.15)²))²)
                  /
                 ( 6e-14
-                    * (1 + 1.75e-3*((675/128 + 273.15)-298.15)
-                        -1.678e-5*((675/128 + 273.15)-298.15)²)
+                    * (1 + 1.75e-3*((675/32 + 273.15)-298.15)
+                        -1.678e-5*((675/32 + 273.15)-298.15)²)
                 )
         ))
     */
@@ -246,6 +257,544 @@ fn can_read_object_temperature_real_data() {
     ];
     let mut tmp = new(&trans);
     let current = tmp.read_object_temperature(6e-14).unwrap();
-    assert!((current - 278.57).abs() < 0.1);
+    assert!((current - 296.12).abs() < 0.1);
     destroy(tmp);
 }
+
+#[test]
+fn can_measure_once() {
+    let trans = [
+        I2cTrans::write(DEV_ADDR, vec![Register::CONFIG, CONFIG_DEFAULT, 0]),
+        I2cTrans::write_read(DEV_ADDR, vec![Register::CONFIG], vec![0, 0]),
+        I2cTrans::write_read(DEV_ADDR, vec![Register::CONFIG], vec![0, CONFIG_RDY_LOW]),
+        I2cTrans::write_read(DEV_ADDR, vec![Register::V_OBJECT], vec![0xFF, 0b1001_1011]),
+        I2cTrans::write_read(DEV_ADDR, vec![Register::TEMP_AMBIENT], vec![0xA, 0x8C]),
+        I2cTrans::write(
+            DEV_ADDR,
+            vec![Register::CONFIG, CONFIG_DEFAULT & !BitFlagsHigh::MOD, 0],
+        ),
+    ];
+    let mut tmp = new(&trans);
+    let data = tmp.measure_once(&mut NoopDelay).unwrap();
+    assert_eq!(
+        SensorData {
+            object_voltage: -101,
+            ambient_temperature: 675
+        },
+        data
+    );
+    destroy(tmp);
+}
+
+#[test]
+fn measure_once_times_out_and_disables() {
+    let config_cps4 = get_config_high(false, false, false);
+    let mut trans = vec![
+        I2cTrans::write(DEV_ADDR, vec![Register::CONFIG, config_cps4, 0]),
+        I2cTrans::write(DEV_ADDR, vec![Register::CONFIG, config_cps4, 0]),
+    ];
+    for _ in 0..=25 {
+        trans.push(I2cTrans::write_read(
+            DEV_ADDR,
+            vec![Register::CONFIG],
+            vec![0, 0],
+        ));
+    }
+    trans.push(I2cTrans::write(
+        DEV_ADDR,
+        vec![Register::CONFIG, config_cps4 & !BitFlagsHigh::MOD, 0],
+    ));
+    let mut tmp = new(&trans);
+    tmp.set_conversion_rate(ConversionRate::Cps4).unwrap();
+    match tmp.measure_once(&mut NoopDelay) {
+        Err(Error::Timeout) => (),
+        _ => panic!("Did not time out."),
+    }
+    destroy(tmp);
+}
+
+#[test]
+fn can_sample_with_scheduler() {
+    let config_cps2 = get_config_high(false, false, true);
+    let trans = [
+        I2cTrans::write(DEV_ADDR, vec![Register::CONFIG, config_cps2, 0]),
+        I2cTrans::write(DEV_ADDR, vec![Register::CONFIG, config_cps2, 0]),
+        I2cTrans::write_read(DEV_ADDR, vec![Register::CONFIG], vec![0, CONFIG_RDY_LOW]),
+        I2cTrans::write_read(DEV_ADDR, vec![Register::V_OBJECT], vec![0, 1]),
+        I2cTrans::write_read(DEV_ADDR, vec![Register::TEMP_AMBIENT], vec![0, 4]),
+        I2cTrans::write(
+            DEV_ADDR,
+            vec![Register::CONFIG, config_cps2 & !BitFlagsHigh::MOD, 0],
+        ),
+    ];
+    let mut tmp = new(&trans);
+    let scheduler = Scheduler::new(10_000, 20.0).unwrap();
+    assert_eq!(ConversionRate::Cps2, scheduler.conversion_rate());
+    let data = scheduler.sample(&mut tmp, &mut NoopDelay).unwrap();
+    assert_eq!(
+        SensorData {
+            object_voltage: 1,
+            ambient_temperature: 1
+        },
+        data
+    );
+    destroy(tmp);
+}
+
+#[test]
+fn can_detect_missed_conversions() {
+    let ready = I2cTrans::write_read(DEV_ADDR, vec![Register::CONFIG], vec![0, CONFIG_RDY_LOW]);
+    let t_amb = I2cTrans::write_read(DEV_ADDR, vec![Register::TEMP_AMBIENT], vec![0, 0]);
+    let mut trans = Vec::new();
+    for v in 0..3 {
+        let v_obj = I2cTrans::write_read(DEV_ADDR, vec![Register::V_OBJECT], vec![0, v]);
+        trans.extend([ready.clone(), v_obj, t_amb.clone()]);
+    }
+    let mut timestamps = [100, 1080, 4120].into_iter();
+    let mut sampler = Sampler::new(new(&trans), move || timestamps.next().unwrap());
+
+    let sample = sampler.read().unwrap();
+    assert_eq!(
+        (100, 0, 0),
+        (sample.timestamp, sample.sequence, sample.missed)
+    );
+    let sample = sampler.read().unwrap();
+    assert_eq!(
+        (1080, 1, 0),
+        (sample.timestamp, sample.sequence, sample.missed)
+    );
+    let sample = sampler.read().unwrap();
+    assert_eq!(
+        (4120, 4, 2),
+        (sample.timestamp, sample.sequence, sample.missed)
+    );
+    assert_eq!(2, sampler.overruns());
+
+    let (tmp, _) = sampler.destroy();
+    destroy(tmp);
+}
+
+#[test]
+fn sampler_reads_identical_consecutive_conversions() {
+    let ready = I2cTrans::write_read(DEV_ADDR, vec![Register::CONFIG], vec![0, CONFIG_RDY_LOW]);
+    let v_obj = I2cTrans::write_read(DEV_ADDR, vec![Register::V_OBJECT], vec![0, 1]);
+    let t_amb = I2cTrans::write_read(DEV_ADDR, vec![Register::TEMP_AMBIENT], vec![0, 0]);
+    let trans = [
+        ready.clone(),
+        v_obj.clone(),
+        t_amb.clone(),
+        ready,
+        v_obj,
+        t_amb,
+    ];
+    let mut timestamps = [100, 300, 1100].into_iter();
+    let mut sampler = Sampler::new(new(&trans), move || timestamps.next().unwrap());
+
+    let first = sampler.read().unwrap();
+    assert_eq!((100, 0, 0), (first.timestamp, first.sequence, first.missed));
+    assert_would_block!(sampler.read());
+    let second = sampler.read().unwrap();
+    assert_eq!(
+        (1100, 1, 0),
+        (second.timestamp, second.sequence, second.missed)
+    );
+    assert_eq!(first.data, second.data);
+    assert_eq!(0, sampler.overruns());
+
+    let (tmp, _) = sampler.destroy();
+    destroy(tmp);
+}
+
+#[test]
+fn can_poll_threshold_monitor() {
+    let trans = [
+        I2cTrans::write_read(DEV_ADDR, vec![Register::CONFIG], vec![0, CONFIG_RDY_LOW]),
+        I2cTrans::write_read(DEV_ADDR, vec![Register::V_OBJECT], vec![0xFF, 0b1001_1011]),
+        I2cTrans::write_read(DEV_ADDR, vec![Register::TEMP_AMBIENT], vec![0xA, 0x8C]),
+    ];
+    let mut tmp = new(&trans);
+    let limits = Limits {
+        low: 0.0,
+        high: 20.0,
+        hysteresis: 1.0,
+        debounce: 1,
+    };
+    let object_limits = Limits {
+        low: 22.0,
+        high: 24.0,
+        ..limits
+    };
+    let mut monitor = ThresholdMonitor::new(Some(object_limits), Some(limits));
+    let events = monitor.poll(&mut tmp, 6e-14).unwrap();
+    assert_eq!(None, events.object);
+    assert_eq!(Some(ThresholdEvent::EnteredHigh), events.ambient);
+    destroy(tmp);
+}
+
+#[test]
+fn suppresses_readings_until_stable() {
+    let ready = I2cTrans::write_read(DEV_ADDR, vec![Register::CONFIG], vec![0, CONFIG_RDY_LOW]);
+    let v_obj = I2cTrans::write_read(DEV_ADDR, vec![Register::V_OBJECT], vec![0, 1]);
+    let t_amb = I2cTrans::write_read(DEV_ADDR, vec![Register::TEMP_AMBIENT], vec![0x0C, 0x80]);
+    let mut trans = Vec::new();
+    for _ in 0..2 {
+        trans.extend([ready.clone(), v_obj.clone(), t_amb.clone()]);
+    }
+    let mut tmp = new(&trans);
+    let mut detector = SettlingDetector::<2>::new(ConversionRate::Cps1, 0.01, 1.0);
+    assert_would_block!(detector.read_when_stable(&mut tmp));
+    let data = detector.read_when_stable(&mut tmp).unwrap();
+    assert_eq!(800, data.ambient_temperature);
+    assert_eq!(SettlingState::Stable, detector.state());
+    destroy(tmp);
+}
+
+#[test]
+fn health_monitor_reports_bus_errors() {
+    let trans = [
+        I2cTrans::write_read(DEV_ADDR, vec![Register::CONFIG], vec![0, CONFIG_RDY_LOW])
+            .with_error(ErrorKind::Other),
+    ];
+    let mut tmp = new(&trans);
+    let mut monitor = HealthMonitor::new(3, 3);
+    assert!(monitor.read(&mut tmp).is_err());
+    assert_eq!(Health::Faulty(Fault::I2C), monitor.health());
+    assert_eq!(1, monitor.counters().i2c);
+    destroy(tmp);
+}
+
+#[test]
+fn ensure_configured_does_nothing_if_configuration_matches() {
+    let trans = [I2cTrans::write_read(
+        DEV_ADDR,
+        vec![Register::CONFIG],
+        vec![CONFIG_DEFAULT, CONFIG_RDY_LOW],
+    )];
+    let mut tmp = new(&trans);
+    assert!(!tmp.ensure_configured().unwrap());
+    assert_eq!(0, tmp.reconfiguration_count());
+    destroy(tmp);
+}
+
+#[test]
+fn ensure_configured_reapplies_configuration() {
+    let config_cps4 = get_config_high(false, false, false);
+    let config = config_cps4 | BitFlagsHigh::DRDY_EN;
+    let trans = [
+        I2cTrans::write(DEV_ADDR, vec![Register::CONFIG, config_cps4, 0]),
+        I2cTrans::write(DEV_ADDR, vec![Register::CONFIG, config, 0]),
+        I2cTrans::write_read(DEV_ADDR, vec![Register::CONFIG], vec![CONFIG_DEFAULT, 0]),
+        I2cTrans::write(DEV_ADDR, vec![Register::CONFIG, config, 0]),
+    ];
+    let mut tmp = new(&trans);
+    tmp.set_conversion_rate(ConversionRate::Cps4).unwrap();
+    tmp.enable_drdy_pin().unwrap();
+    assert!(tmp.ensure_configured().unwrap());
+    assert_eq!(1, tmp.reconfiguration_count());
+    destroy(tmp);
+}
+
+#[test]
+fn checks_configuration_every_n_reads() {
+    let config_cps4 = get_config_high(false, false, false);
+    let ready = I2cTrans::write_read(DEV_ADDR, vec![Register::CONFIG], vec![0, CONFIG_RDY_LOW]);
+    let v_obj = I2cTrans::write_read(DEV_ADDR, vec![Register::V_OBJECT], vec![0, 0]);
+    let t_amb = I2cTrans::write_read(DEV_ADDR, vec![Register::TEMP_AMBIENT], vec![0, 0]);
+    let trans = [
+        I2cTrans::write(DEV_ADDR, vec![Register::CONFIG, config_cps4, 0]),
+        ready.clone(),
+        v_obj.clone(),
+        t_amb.clone(),
+        I2cTrans::write_read(DEV_ADDR, vec![Register::CONFIG], vec![CONFIG_DEFAULT, 0]),
+        I2cTrans::write(DEV_ADDR, vec![Register::CONFIG, config_cps4, 0]),
+        ready,
+        v_obj,
+        t_amb,
+    ];
+    let mut tmp = new(&trans);
+    tmp.set_conversion_rate(ConversionRate::Cps4).unwrap();
+    tmp.set_config_check_interval(2);
+    tmp.read_sensor_data().unwrap();
+    assert_would_block!(tmp.read_sensor_data());
+    tmp.read_sensor_data().unwrap();
+    assert_eq!(1, tmp.reconfiguration_count());
+    destroy(tmp);
+}
+
+#[test]
+fn retries_transient_errors() {
+    let nack = ErrorKind::NoAcknowledge(NoAcknowledgeSource::Address);
+    let trans = [
+        I2cTrans::write(DEV_ADDR, vec![Register::CONFIG, CONFIG_DEFAULT, 0]).with_error(nack),
+        I2cTrans::write(DEV_ADDR, vec![Register::CONFIG, CONFIG_DEFAULT, 0])
+            .with_error(ErrorKind::ArbitrationLoss),
+        I2cTrans::write(DEV_ADDR, vec![Register::CONFIG, CONFIG_DEFAULT, 0]),
+        I2cTrans::write_read(DEV_ADDR, vec![Register::MANUFAC_ID], vec![0x54, 0x49])
+            .with_error(nack),
+        I2cTrans::write_read(DEV_ADDR, vec![Register::MANUFAC_ID], vec![0x54, 0x49]),
+    ];
+    let mut tmp = new(&trans).with_retry_policy(RetryPolicy::new(2, 100), NoopDelay);
+    tmp.enable().unwrap();
+    assert_eq!(0x5449, tmp.read_manufacturer_id().unwrap());
+    assert_eq!(3, tmp.error_count());
+    assert_eq!(3, tmp.retry_count());
+    tmp.destroy().done();
+}
+
+#[test]
+fn gives_up_after_retries() {
+    let nack = ErrorKind::NoAcknowledge(NoAcknowledgeSource::Data);
+    let write = I2cTrans::write(DEV_ADDR, vec![Register::CONFIG, CONFIG_DEFAULT, 0]);
+    let trans = [
+        write.clone().with_error(nack),
+        write.clone().with_error(nack),
+        write.with_error(ErrorKind::Bus),
+    ];
+    let mut tmp = new(&trans).with_retry_policy(RetryPolicy::new(5, 100), NoopDelay);
+    match tmp.enable() {
+        Err(Error::I2C(ErrorKind::Bus)) => (),
+        _ => panic!("Did not fail with a bus error."),
+    }
+    assert_eq!(3, tmp.error_count());
+    assert_eq!(2, tmp.retry_count());
+    tmp.destroy().done();
+}
+
+#[test]
+fn does_not_retry_without_policy() {
+    let trans = [
+        I2cTrans::write(DEV_ADDR, vec![Register::CONFIG, CONFIG_DEFAULT, 0])
+            .with_error(ErrorKind::ArbitrationLoss),
+    ];
+    let mut tmp = new(&trans);
+    assert!(tmp.enable().is_err());
+    assert_eq!(1, tmp.error_count());
+    assert_eq!(0, tmp.retry_count());
+    destroy(tmp);
+}
+
+#[test]
+fn can_verify_writes() {
+    let config = get_config_high(false, false, true);
+    let trans = [
+        I2cTrans::write(DEV_ADDR, vec![Register::CONFIG, config, 0]),
+        I2cTrans::write_read(
+            DEV_ADDR,
+            vec![Register::CONFIG],
+            vec![config, CONFIG_RDY_LOW],
+        ),
+        I2cTrans::write(
+            DEV_ADDR,
+            vec![Register::CONFIG, config | BitFlagsHigh::SW_RESET, 0],
+        ),
+        I2cTrans::write_read(DEV_ADDR, vec![Register::CONFIG], vec![CONFIG_DEFAULT, 0]),
+    ];
+    let mut tmp = new(&trans);
+    tmp.enable_write_verification();
+    tmp.set_conversion_rate(ConversionRate::Cps2).unwrap();
+    tmp.reset().unwrap();
+    destroy(tmp);
+}
+
+#[test]
+fn verified_write_fails_on_mismatch() {
+    let config = get_config_high(false, false, true);
+    let trans = [
+        I2cTrans::write(DEV_ADDR, vec![Register::CONFIG, config, 0]),
+        I2cTrans::write_read(DEV_ADDR, vec![Register::CONFIG], vec![CONFIG_DEFAULT, 0]),
+        I2cTrans::write_read(DEV_ADDR, vec![Register::CONFIG], vec![CONFIG_DEFAULT, 0]),
+    ];
+    let mut tmp = new(&trans);
+    tmp.enable_write_verification();
+    match tmp.set_conversion_rate(ConversionRate::Cps2) {
+        Err(Error::VerificationFailed { expected, actual }) => {
+            assert_eq!(config, expected);
+            assert_eq!(CONFIG_DEFAULT, actual);
+        }
+        _ => panic!("Verification did not fail."),
+    }
+    // the configuration is not cached after a failed verification
+    assert!(!tmp.ensure_configured().unwrap());
+    destroy(tmp);
+}
+
+#[test]
+fn can_dump_registers() {
+    let trans = [
+        I2cTrans::write_read(DEV_ADDR, vec![Register::V_OBJECT], vec![0xFF, 0x9C]),
+        I2cTrans::write_read(DEV_ADDR, vec![Register::TEMP_AMBIENT], vec![0x0A, 0x8C]),
+        I2cTrans::write_read(
+            DEV_ADDR,
+            vec![Register::CONFIG],
+            vec![CONFIG_DEFAULT, CONFIG_RDY_LOW],
+        ),
+        I2cTrans::write_read(DEV_ADDR, vec![Register::MANUFAC_ID], vec![0x54, 0x49]),
+        I2cTrans::write_read(DEV_ADDR, vec![Register::DEVICE_ID], vec![0x00, 0x67]),
+    ];
+    let mut tmp = new(&trans);
+    let snapshot = tmp.dump_registers().unwrap();
+    assert_eq!(
+        "V_OBJECT  (0x00): 0xFF9C (-15.625 µV)\n\
+         T_AMBIENT (0x01): 0x0A8C (21.09375 °C)\n\
+         CONFIG    (0x02): 0x7480 (mode: continuous, rate: 1 conversions/s, DRDY_EN: 0, DRDY: 1)\n\
+         MANUF_ID  (0xFE): 0x5449\n\
+         DEVICE_ID (0xFF): 0x0067",
+        format!("{}", snapshot)
+    );
+    destroy(tmp);
+}
+
+#[test]
+fn coherent_read_repeats_if_conversion_finished_in_between() {
+    let trans = [
+        I2cTrans::write_read(DEV_ADDR, vec![Register::CONFIG], vec![0, CONFIG_RDY_LOW]),
+        I2cTrans::write_read(DEV_ADDR, vec![Register::V_OBJECT], vec![0xFF, 0x9C]),
+        I2cTrans::write_read(DEV_ADDR, vec![Register::TEMP_AMBIENT], vec![0x0A, 0x90]),
+        I2cTrans::write_read(DEV_ADDR, vec![Register::V_OBJECT], vec![0xFF, 0x9D]),
+        I2cTrans::write_read(DEV_ADDR, vec![Register::V_OBJECT], vec![0xFF, 0x9D]),
+        I2cTrans::write_read(DEV_ADDR, vec![Register::TEMP_AMBIENT], vec![0x0A, 0x90]),
+        I2cTrans::write_read(DEV_ADDR, vec![Register::V_OBJECT], vec![0xFF, 0x9D]),
+    ];
+    let mut tmp = new(&trans);
+    let data = tmp.read_sensor_data_coherent(1).unwrap();
+    assert_eq!(SensorData::from_registers(0xFF9D, 0x0A90), data);
+    destroy(tmp);
+}
+
+#[test]
+fn coherent_read_gives_up_after_retries() {
+    let trans = [
+        I2cTrans::write_read(DEV_ADDR, vec![Register::CONFIG], vec![0, CONFIG_RDY_LOW]),
+        I2cTrans::write_read(DEV_ADDR, vec![Register::V_OBJECT], vec![0xFF, 0x9C]),
+        I2cTrans::write_read(DEV_ADDR, vec![Register::TEMP_AMBIENT], vec![0x0A, 0x90]),
+        I2cTrans::write_read(DEV_ADDR, vec![Register::V_OBJECT], vec![0xFF, 0x9D]),
+    ];
+    let mut tmp = new(&trans);
+    let result = tmp.read_sensor_data_coherent(0);
+    assert_would_block!(result);
+    destroy(tmp);
+}
+
+#[test]
+fn can_read_ambient_temperature_without_data_ready() {
+    let trans = [I2cTrans::write_read(
+        DEV_ADDR,
+        vec![Register::TEMP_AMBIENT],
+        vec![0xF9, 0x60],
+    )];
+    let mut tmp = new(&trans);
+    assert_eq!(-13.25, tmp.read_ambient_temperature().unwrap());
+    destroy(tmp);
+}
+
+#[test]
+fn can_read_object_voltage_without_data_ready() {
+    let trans = [I2cTrans::write_read(
+        DEV_ADDR,
+        vec![Register::V_OBJECT],
+        vec![0xFF, 0x9C],
+    )];
+    let mut tmp = new(&trans);
+    assert_eq!(-100, tmp.read_object_voltage().unwrap());
+    destroy(tmp);
+}
+
+#[test]
+fn fresh_read_does_not_return_same_conversion_twice() {
+    let ready = I2cTrans::write_read(
+        DEV_ADDR,
+        vec![Register::CONFIG],
+        vec![CONFIG_DEFAULT, CONFIG_RDY_LOW],
+    );
+    let not_ready = I2cTrans::write_read(DEV_ADDR, vec![Register::CONFIG], vec![CONFIG_DEFAULT, 0]);
+    let v_obj = I2cTrans::write_read(DEV_ADDR, vec![Register::V_OBJECT], vec![0xFF, 0x9C]);
+    let t_amb = I2cTrans::write_read(DEV_ADDR, vec![Register::TEMP_AMBIENT], vec![0x0A, 0x90]);
+    let trans = [
+        ready.clone(),
+        v_obj.clone(),
+        t_amb.clone(),
+        // identical data from the next conversion
+        ready.clone(),
+        v_obj.clone(),
+        t_amb.clone(),
+        not_ready,
+        ready,
+        v_obj,
+        t_amb,
+    ];
+    let mut tmp = new(&trans);
+    let data = tmp.read_fresh_sensor_data(100).unwrap();
+    assert_eq!(SensorData::from_registers(0xFF9C, 0x0A90), data);
+    assert_eq!(1, tmp.fresh_read_count());
+    assert_would_block!(tmp.read_fresh_sensor_data(600));
+    assert_eq!(1, tmp.fresh_read_count());
+    assert_eq!(data, tmp.read_fresh_sensor_data(1150).unwrap());
+    assert_eq!(2, tmp.fresh_read_count());
+    assert_would_block!(tmp.read_fresh_sensor_data(2100));
+    assert_eq!(data, tmp.read_fresh_sensor_data(2120).unwrap());
+    assert_eq!(3, tmp.fresh_read_count());
+    destroy(tmp);
+}
+
+#[test]
+fn fresh_read_follows_conversion_rate() {
+    let config_cps4 = get_config_high(false, false, false);
+    let ready = I2cTrans::write_read(
+        DEV_ADDR,
+        vec![Register::CONFIG],
+        vec![config_cps4, CONFIG_RDY_LOW],
+    );
+    let v_obj = I2cTrans::write_read(DEV_ADDR, vec![Register::V_OBJECT], vec![0, 1]);
+    let t_amb = I2cTrans::write_read(DEV_ADDR, vec![Register::TEMP_AMBIENT], vec![0, 4]);
+    let trans = [
+        I2cTrans::write_read(DEV_ADDR, vec![Register::CONFIG], vec![config_cps4, 0]),
+        ready.clone(),
+        v_obj.clone(),
+        t_amb.clone(),
+        ready,
+        v_obj,
+        t_amb,
+    ];
+    let mut tmp = new(&trans);
+    tmp.load_config().unwrap();
+    tmp.read_fresh_sensor_data(0).unwrap();
+    assert_would_block!(tmp.read_fresh_sensor_data(249));
+    tmp.read_fresh_sensor_data(250).unwrap();
+    destroy(tmp);
+}
+
+#[test]
+fn can_check_device_id() {
+    let trans = [
+        I2cTrans::write_read(DEV_ADDR, vec![Register::MANUFAC_ID], vec![0x54, 0x49]),
+        I2cTrans::write_read(DEV_ADDR, vec![Register::DEVICE_ID], vec![0x00, 0x67]),
+    ];
+    let mut tmp = new(&trans);
+    tmp.check_device_id().unwrap();
+    destroy(tmp);
+}
+
+#[test]
+fn check_device_id_fails_on_mismatch() {
+    let trans = [
+        I2cTrans::write_read(DEV_ADDR, vec![Register::MANUFAC_ID], vec![0x54, 0x49]),
+        I2cTrans::write_read(DEV_ADDR, vec![Register::DEVICE_ID], vec![0x00, 0x78]),
+    ];
+    let mut tmp = new(&trans);
+    let error = tmp.check_device_id().unwrap_err();
+    assert_eq!(tmp006::ErrorKind::InvalidDeviceId, error.kind());
+    assert_eq!(
+        "invalid device ID: manufacturer ID 0x5449, device ID 0x0078",
+        error.to_string()
+    );
+    destroy(tmp);
+}
+
+#[test]
+fn errors_can_be_boxed() {
+    let error: Box<dyn std::error::Error> = Box::new(Error::I2C(ErrorKind::Bus));
+    assert_eq!("I²C bus error: Bus", error.to_string());
+    let error: Error<ErrorKind> = Error::Timeout;
+    assert_eq!(tmp006::ErrorKind::Timeout, error.kind());
+    assert_eq!("timed out waiting for the device", error.to_string());
+}
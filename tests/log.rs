@@ -0,0 +1,74 @@
+#![cfg(feature = "log")]
+
+use embedded_hal::i2c::ErrorKind;
+use embedded_hal_mock::eh1::i2c::{Mock as I2cMock, Transaction as I2cTrans};
+use log::{Level, Log, Metadata, Record};
+use std::sync::Mutex;
+use tmp006::{ConversionRate, SlaveAddr, Tmp006};
+
+const DEV_ADDR: u8 = 0b100_0000;
+
+struct Logger {
+    records: Mutex<Vec<(Level, String)>>,
+}
+
+impl Log for Logger {
+    fn enabled(&self, _metadata: &Metadata) -> bool {
+        true
+    }
+
+    fn log(&self, record: &Record) {
+        let message = record.args().to_string();
+        if message.starts_with("tmp006") {
+            let mut records = self.records.lock().unwrap();
+            records.push((record.level(), message));
+        }
+    }
+
+    fn flush(&self) {}
+}
+
+static LOGGER: Logger = Logger {
+    records: Mutex::new(Vec::new()),
+};
+
+#[test]
+fn logs_register_accesses_and_failures() {
+    log::set_logger(&LOGGER).unwrap();
+    log::set_max_level(log::LevelFilter::Debug);
+
+    let trans = [
+        I2cTrans::write(DEV_ADDR, vec![0x02, 0x70, 0]),
+        I2cTrans::write_read(DEV_ADDR, vec![0xFF], vec![0x00, 0x67]),
+        I2cTrans::write_read(DEV_ADDR, vec![0xFE], vec![0, 0]).with_error(ErrorKind::Bus),
+    ];
+    let mut tmp = Tmp006::new(I2cMock::new(&trans), SlaveAddr::default());
+    tmp.set_conversion_rate(ConversionRate::Cps4).unwrap();
+    tmp.read_device_id().unwrap();
+    tmp.read_manufacturer_id().unwrap_err();
+    tmp.destroy().done();
+
+    let records = LOGGER.records.lock().unwrap();
+    assert_eq!(
+        *records,
+        [
+            (
+                Level::Debug,
+                "tmp006 0x40: wrote Config (0x02): 0x7000".to_string()
+            ),
+            (
+                Level::Debug,
+                "tmp006 0x40: configuration: enabled: true, conversion rate: Cps4, DRDY pin: false"
+                    .to_string()
+            ),
+            (
+                Level::Debug,
+                "tmp006 0x40: read DeviceId (0xFF): 0x0067".to_string()
+            ),
+            (
+                Level::Warn,
+                "tmp006 0x40: I2C transfer failed: Bus (attempt 1)".to_string()
+            ),
+        ]
+    );
+}
@@ -0,0 +1,65 @@
+#![cfg(feature = "unstable-registers")]
+
+use embedded_hal_mock::eh1::i2c::{Mock as I2cMock, Transaction as I2cTrans};
+use tmp006::{BitFlagsHigh, BitFlagsLow, Register, SlaveAddr, Tmp006};
+
+const DEV_ADDR: u8 = 0b100_0000;
+
+fn new(transactions: &[I2cTrans]) -> Tmp006<I2cMock> {
+    Tmp006::new(I2cMock::new(transactions), SlaveAddr::default())
+}
+
+fn destroy(tmp: Tmp006<I2cMock>) {
+    tmp.destroy().done();
+}
+
+#[test]
+fn can_read_result_registers_without_data_ready_check() {
+    let trans = [
+        I2cTrans::write_read(DEV_ADDR, vec![0x00], vec![0xFF, 0x9B]),
+        I2cTrans::write_read(DEV_ADDR, vec![0x01], vec![0x0A, 0x8C]),
+    ];
+    let mut tmp = new(&trans);
+    assert_eq!(
+        0xFF9B,
+        tmp.read_raw_register(Register::ObjectVoltage).unwrap()
+    );
+    assert_eq!(
+        0x0A8C,
+        tmp.read_raw_register(Register::AmbientTemperature).unwrap()
+    );
+    destroy(tmp);
+}
+
+#[test]
+fn can_read_id_registers() {
+    let trans = [
+        I2cTrans::write_read(DEV_ADDR, vec![0xFE], vec![0x54, 0x49]),
+        I2cTrans::write_read(DEV_ADDR, vec![0xFF], vec![0x00, 0x67]),
+    ];
+    let mut tmp = new(&trans);
+    assert_eq!(
+        0x5449,
+        tmp.read_raw_register(Register::ManufacturerId).unwrap()
+    );
+    assert_eq!(0x0067, tmp.read_raw_register(Register::DeviceId).unwrap());
+    destroy(tmp);
+}
+
+#[test]
+fn writing_config_register_updates_driver_config() {
+    let config = BitFlagsHigh::MOD | BitFlagsHigh::CR2 | BitFlagsHigh::DRDY_EN;
+    let trans = [
+        I2cTrans::write(DEV_ADDR, vec![0x02, config, 0]),
+        I2cTrans::write(DEV_ADDR, vec![0x02, config & !BitFlagsHigh::MOD, 0]),
+    ];
+    let mut tmp = new(&trans);
+    tmp.write_raw_register(
+        Register::Config,
+        u16::from_be_bytes([config, BitFlagsLow::DRDY]),
+    )
+    .unwrap();
+    assert_eq!(config, tmp.config_register_msb());
+    tmp.disable().unwrap();
+    destroy(tmp);
+}
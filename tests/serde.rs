@@ -0,0 +1,142 @@
+#![cfg(feature = "serde")]
+
+use tmp006::{
+    filter::{DataFilter, Exponential, Filter, Kalman, Median, MovingAverage},
+    ConversionRate, HealthMonitor, Limits, PresenceDetector, RetryPolicy, Sample, Scheduler,
+    SensorData, SettlingDetector, SlaveAddr, Statistics, ThresholdMonitor,
+};
+
+#[test]
+fn can_serialize_sensor_data() {
+    let data = SensorData {
+        object_voltage: -100,
+        ambient_temperature: 675,
+    };
+    let json = serde_json::to_string(&data).unwrap();
+    assert_eq!(r#"{"object_voltage":-100,"ambient_temperature":675}"#, json);
+    assert_eq!(data, serde_json::from_str(&json).unwrap());
+}
+
+#[test]
+fn can_serialize_configuration() {
+    let json = serde_json::to_string(&ConversionRate::Cps0_5).unwrap();
+    assert_eq!(r#""Cps0_5""#, json);
+    assert_eq!(
+        ConversionRate::Cps0_5,
+        serde_json::from_str::<ConversionRate>(&json).unwrap()
+    );
+
+    let address = SlaveAddr::Alternative(true, false, true);
+    let json = serde_json::to_string(&address).unwrap();
+    assert_eq!(address, serde_json::from_str(&json).unwrap());
+
+    let policy = RetryPolicy::new(3, 100);
+    let json = serde_json::to_string(&policy).unwrap();
+    assert_eq!(policy, serde_json::from_str(&json).unwrap());
+
+    let limits = Limits {
+        low: 10.0,
+        high: 40.0,
+        hysteresis: 0.5,
+        debounce: 2,
+    };
+    let json = serde_json::to_string(&limits).unwrap();
+    assert_eq!(limits, serde_json::from_str(&json).unwrap());
+}
+
+#[test]
+fn can_serialize_sample() {
+    let sample = Sample {
+        timestamp: 1000,
+        sequence: 3,
+        missed: 1,
+        data: SensorData {
+            object_voltage: 12,
+            ambient_temperature: -34,
+        },
+    };
+    let json = serde_json::to_string(&sample).unwrap();
+    assert_eq!(sample, serde_json::from_str(&json).unwrap());
+}
+
+#[test]
+fn can_serialize_filters() {
+    let mut median = Median::<4>::with_duration(ConversionRate::Cps4, 750);
+    for value in [1.0, 2.0, 3.0, 4.0] {
+        median.update(value);
+    }
+    let json = serde_json::to_string(&median).unwrap();
+    assert_eq!("[3,[2.0,3.0,4.0]]", json);
+    assert_eq!(median, serde_json::from_str(&json).unwrap());
+
+    let mut average = MovingAverage::<2>::new();
+    average.update(1.0);
+    let json = serde_json::to_string(&average).unwrap();
+    assert_eq!(average, serde_json::from_str(&json).unwrap());
+
+    let mut filter = DataFilter::new(Exponential::new(0.5), Exponential::new(0.5));
+    filter.update(SensorData::default());
+    let json = serde_json::to_string(&filter).unwrap();
+    assert_eq!(filter, serde_json::from_str(&json).unwrap());
+
+    let mut kalman = Kalman::with_conversion_rate(ConversionRate::Cps1, 1.0, 0.1);
+    kalman.update(20.0);
+    let json = serde_json::to_string(&kalman).unwrap();
+    assert_eq!(kalman, serde_json::from_str(&json).unwrap());
+}
+
+#[test]
+fn rejects_windows_larger_than_capacity() {
+    assert!(serde_json::from_str::<MovingAverage<2>>("[3,[]]").is_err());
+    assert!(serde_json::from_str::<MovingAverage<2>>("[2,[1.0,2.0,3.0]]").is_err());
+    assert!(serde_json::from_str::<Statistics<2>>("[2,[1.0,2.0]]").is_ok());
+}
+
+#[test]
+fn rejects_conversions_longer_than_sampling_interval() {
+    assert!(serde_json::from_str::<Scheduler>(
+        r#"{"conversion_rate":"Cps0_25","interval_ms":100}"#
+    )
+    .is_err());
+    assert!(
+        serde_json::from_str::<Scheduler>(r#"{"conversion_rate":"Cps4","interval_ms":0}"#).is_err()
+    );
+    assert_eq!(
+        Scheduler::with_conversion_rate(ConversionRate::Cps4, 250),
+        serde_json::from_str(r#"{"conversion_rate":"Cps4","interval_ms":250}"#).ok()
+    );
+}
+
+#[test]
+fn can_serialize_monitors() {
+    let scheduler = Scheduler::new(10_000, 20.0).unwrap();
+    let json = serde_json::to_string(&scheduler).unwrap();
+    assert_eq!(scheduler, serde_json::from_str(&json).unwrap());
+
+    let limits = Limits {
+        low: 10.0,
+        high: 40.0,
+        hysteresis: 0.5,
+        debounce: 2,
+    };
+    let mut threshold = ThresholdMonitor::new(Some(limits), None);
+    threshold.update(45.0, 20.0);
+    let json = serde_json::to_string(&threshold).unwrap();
+    assert_eq!(threshold, serde_json::from_str(&json).unwrap());
+
+    let mut presence = PresenceDetector::new(2.0, 0.5, Exponential::new(0.1)).with_timeout(10);
+    presence.update(20.0);
+    let json = serde_json::to_string(&presence).unwrap();
+    assert_eq!(presence, serde_json::from_str(&json).unwrap());
+
+    let mut settling = SettlingDetector::<4>::new(ConversionRate::Cps1, 0.01, 2.0);
+    settling.update(SensorData::default());
+    let json = serde_json::to_string(&settling).unwrap();
+    assert_eq!(settling, serde_json::from_str(&json).unwrap());
+
+    let mut health = HealthMonitor::new(3, 3);
+    let result: nb::Result<_, tmp006::Error<()>> = Ok((0, 0));
+    health.check(&result);
+    let json = serde_json::to_string(&health).unwrap();
+    assert_eq!(health, serde_json::from_str(&json).unwrap());
+}
@@ -0,0 +1,123 @@
+#![cfg(feature = "tracing")]
+
+use embedded_hal::i2c::ErrorKind;
+use embedded_hal_mock::eh1::i2c::{Mock as I2cMock, Transaction as I2cTrans};
+use std::fmt;
+use std::sync::{Arc, Mutex};
+use tmp006::{ConversionRate, SlaveAddr, Tmp006};
+use tracing::field::{Field, Visit};
+use tracing::span::{Attributes, Id, Record};
+use tracing::{Event, Level, Metadata, Subscriber};
+
+const DEV_ADDR: u8 = 0b100_0000;
+
+#[derive(Clone, Default)]
+struct Collector {
+    records: Arc<Mutex<Vec<(Level, String)>>>,
+}
+
+struct Message(String);
+
+impl Visit for Message {
+    fn record_debug(&mut self, field: &Field, value: &dyn fmt::Debug) {
+        if field.name() == "message" {
+            self.0 = format!("{:?}", value);
+        }
+    }
+}
+
+impl Subscriber for Collector {
+    fn enabled(&self, _metadata: &Metadata<'_>) -> bool {
+        true
+    }
+
+    fn new_span(&self, _span: &Attributes<'_>) -> Id {
+        Id::from_u64(1)
+    }
+
+    fn record(&self, _span: &Id, _values: &Record<'_>) {}
+
+    fn record_follows_from(&self, _span: &Id, _follows: &Id) {}
+
+    fn event(&self, event: &Event<'_>) {
+        let mut message = Message(String::new());
+        event.record(&mut message);
+        if message.0.starts_with("tmp006") {
+            let mut records = self.records.lock().unwrap();
+            records.push((*event.metadata().level(), message.0));
+        }
+    }
+
+    fn enter(&self, _span: &Id) {}
+
+    fn exit(&self, _span: &Id) {}
+}
+
+#[test]
+fn traces_register_accesses_and_failures() {
+    let collector = Collector::default();
+    let trans = [
+        I2cTrans::write(DEV_ADDR, vec![0x02, 0x70, 0]),
+        I2cTrans::write_read(DEV_ADDR, vec![0xFF], vec![0x00, 0x67]),
+        I2cTrans::write_read(DEV_ADDR, vec![0xFE], vec![0, 0]).with_error(ErrorKind::Bus),
+    ];
+    tracing::subscriber::with_default(collector.clone(), || {
+        let mut tmp = Tmp006::new(I2cMock::new(&trans), SlaveAddr::default());
+        tmp.set_conversion_rate(ConversionRate::Cps4).unwrap();
+        tmp.read_device_id().unwrap();
+        tmp.read_manufacturer_id().unwrap_err();
+        tmp.destroy().done();
+    });
+
+    let records = collector.records.lock().unwrap();
+    assert_eq!(
+        *records,
+        [
+            (
+                Level::DEBUG,
+                "tmp006 0x40: wrote Config (0x02): 0x7000".to_string()
+            ),
+            (
+                Level::DEBUG,
+                "tmp006 0x40: configuration: enabled: true, conversion rate: Cps4, DRDY pin: false"
+                    .to_string()
+            ),
+            (
+                Level::DEBUG,
+                "tmp006 0x40: read DeviceId (0xFF): 0x0067".to_string()
+            ),
+            (
+                Level::WARN,
+                "tmp006 0x40: I2C transfer failed: Bus (attempt 1)".to_string()
+            ),
+        ]
+    );
+}
+
+#[cfg(feature = "async")]
+#[test]
+fn traces_async_configuration_changes() {
+    let collector = Collector::default();
+    let trans = [I2cTrans::write(DEV_ADDR, vec![0x02, 0x70, 0])];
+    tracing::subscriber::with_default(collector.clone(), || {
+        let mut tmp = tmp006::Tmp006Async::new(I2cMock::new(&trans), SlaveAddr::default());
+        futures::executor::block_on(tmp.set_conversion_rate(ConversionRate::Cps4)).unwrap();
+        tmp.destroy().done();
+    });
+
+    let records = collector.records.lock().unwrap();
+    assert_eq!(
+        *records,
+        [
+            (
+                Level::DEBUG,
+                "tmp006 0x40: wrote Config (0x02): 0x7000".to_string()
+            ),
+            (
+                Level::DEBUG,
+                "tmp006 0x40: configuration: enabled: true, conversion rate: Cps4, DRDY pin: false"
+                    .to_string()
+            ),
+        ]
+    );
+}